@@ -1,14 +1,26 @@
 use std::env;
 use std::fs;
 
+/// Used when `TOKEN_EXPIRY_SKEW_SECS` isn't set
+const DEFAULT_TOKEN_EXPIRY_SKEW_SECS: f64 = 30.0;
+
 fn main() {
     println!("cargo:rerun-if-changed=.env");
     dotenvy::dotenv().ok();
     let url = env::var("API_BASE_URL").expect("API_BASE_URL must be set");
+    let skew_secs = match env::var("TOKEN_EXPIRY_SKEW_SECS") {
+        Ok(v) => v
+            .parse::<f64>()
+            .ok()
+            .filter(|s| s.is_finite() && *s >= 0.0)
+            .expect("TOKEN_EXPIRY_SKEW_SECS must be a non-negative number of seconds"),
+        Err(_) => DEFAULT_TOKEN_EXPIRY_SKEW_SECS,
+    };
 
     let content = format!(
-        "// auto-generated\npub const API_BASE_URL: &str = \"{}\";\n",
-        url.replace('"', "\\\"")
+        "// auto-generated\npub const API_BASE_URL: &str = \"{}\";\npub const TOKEN_EXPIRY_SKEW_SECS: f64 = {:?};\n",
+        url.replace('"', "\\\""),
+        skew_secs
     );
 
     fs::write("src/env.rs", content).unwrap();
@@ -1,39 +1,60 @@
 use std::str::FromStr;
 
 use gloo_net::http::Method;
-use leptos::{prelude::*, reactive::spawn_local};
+use leptos::{html, prelude::*, reactive::spawn_local};
 use strum::IntoEnumIterator;
 
 use crate::{
-    product::{Cents, FilamentColor, FilamentDiameter, FilamentMaterial, Grams, Product, Retailer},
-    request::{Auth, request_json},
-    session::Session,
+    admin_audit::AdminAudit,
+    admin_list::AdminProductList,
+    admin_quality::AdminDataQuality,
+    color_swatch::{ColorChip, ColorSwatchPicker},
+    confirm::TypedConfirm,
+    product::{
+        Cents, Currency, FieldError, FilamentColor, FilamentDiameter, FilamentMaterial, Grams,
+        MAX_DESCRIPTION_LEN, MAX_DIAMETER_H, MAX_OTHER_LEN, MIN_DIAMETER_H, Product, ProductField,
+        Region, Retailer, TemperatureSpec, WeightBasis, clamp_other, normalize_name, normalize_url,
+    },
+    product_search::{focus_when_shown, other_length_hint},
+    request::{Auth, ErrorResponse, new_idempotency_key, request_json, request_json_with_headers},
+    resource::{LoadState, use_resource},
+    session::use_is_admin,
+    tag_input::TagInput,
+    taxonomy::use_taxonomy,
+    toast::use_toasts,
 };
 
 #[component]
 pub fn AdminPage() -> impl IntoView {
-    let mut redirect = true;
-
-    if let Some(session) = Session::load()
-        && session.is_admin
-    {
-        redirect = false;
-    }
-
-    if redirect {
+    let is_admin = use_is_admin();
+    let to_login = || {
         web_sys::window()
             .expect("No global window")
             .location()
             .set_href("/login")
             .expect("Failed to redirect to login page");
+    };
 
+    if !is_admin.get_untracked() {
+        to_login();
         return ().into_any();
     }
 
+    // Logging out, or losing admin rights, in another tab shouldn't leave the tools usable here
+    Effect::new(move |_| {
+        if !is_admin.get() {
+            to_login();
+        }
+    });
+
     view! {
         <div class="container">
             <h1>"Admin"</h1>
+            <FindProductByUrl />
             <ProductEditor product_id=None />
+            <AdminProductList />
+            <AdminDataQuality />
+            <AdminAudit />
         </div>
     }
     .into_any()
@@ -45,6 +66,79 @@ enum ResultMessage {
     Error(String),
 }
 
+/// Looks up a product by its retailer page URL and opens it in the editor
+#[component]
+fn FindProductByUrl() -> impl IntoView {
+    let (url, set_url) = signal(String::new());
+    let (searching, set_searching) = signal(false);
+    let (result_message, set_result_message) = signal::<Option<ResultMessage>>(None);
+    let navigate = leptos_router::hooks::use_navigate();
+
+    let on_find = move |_| {
+        let url = url.get_untracked().trim().to_string();
+
+        if url.is_empty() {
+            return;
+        }
+
+        let encode = |s: &str| String::from(web_sys::js_sys::encode_uri_component(s));
+        let mut path = format!("products/by-url?url={}", encode(&url));
+
+        if let Some(id) = Retailer::from_url(&url).and_then(|r| r.product_id_from_url(&url)) {
+            path.push_str(&format!("&retailer_product_id={}", encode(&id)));
+        }
+
+        let navigate = navigate.clone();
+        set_searching.set(true);
+        set_result_message.set(None);
+
+        spawn_local(async move {
+            match request_json::<(), Product>(&path, Auth::Authorized, Method::GET, None).await {
+                Ok(p) => navigate(&format!("/admin?product={}", p.uuid), Default::default()),
+                Err(e) if e.status == 404 => {
+                    set_result_message.set(Some(ResultMessage::Error(
+                        "No product found for that URL".to_string(),
+                    )));
+                }
+                Err(e) => {
+                    set_result_message.set(Some(ResultMessage::Error(format!(
+                        "Failed to look up product: ({}) {}",
+                        e.status, e.message
+                    ))));
+                }
+            }
+
+            set_searching.set(false);
+        });
+    };
+
+    view! {
+        <div class="container full-width">
+            <h2>"Find by URL"</h2>
+            <div class="options-row">
+                <input
+                    class="input"
+                    type="text"
+                    placeholder="https://www.amazon.com/dp/B0XXXXXXXX"
+                    prop:value=move || url.get()
+                    on:input=move |e| set_url.set(event_target_value(&e))
+                />
+                <div>
+                    <button disabled=move || searching.get() on:click=on_find>
+                        {move || if searching.get() { "Finding…" } else { "Find" }}
+                    </button>
+                </div>
+            </div>
+            <Show when=move || result_message.get().is_some()>
+                {move || match result_message.get().unwrap() {
+                    ResultMessage::Success(s) => view! { <p class="success">{s}</p> }.into_view(),
+                    ResultMessage::Error(s) => view! { <p class="error">{s}</p> }.into_view(),
+                }}
+            </Show>
+        </div>
+    }
+}
+
 #[component]
 pub fn ProductEditor(product_id: Option<String>) -> impl IntoView {
     let (uuid, set_uuid) = signal::<String>(product_id.unwrap_or_default());
@@ -53,167 +147,438 @@ pub fn ProductEditor(product_id: Option<String>) -> impl IntoView {
     let (material, set_material) = signal::<FilamentMaterial>(FilamentMaterial::Unspecified);
     let (retailer, set_retailer) = signal::<Retailer>(Retailer::Other(String::new()));
     let (retailer_pid, set_retailer_pid) = signal::<String>(String::new());
-    let (diameter, set_diameter) = signal::<FilamentDiameter>(FilamentDiameter::D175);
+    // Set once the admin types their own retailer product ID, so URL edits stop replacing it
+    let (pid_overridden, set_pid_overridden) = signal(false);
+    let (diameter, set_diameter) = signal::<FilamentDiameter>(FilamentDiameter::Unspecified);
+    // Text of the custom diameter input, kept separately so typing isn't reformatted mid-entry
+    let (diameter_other_string, set_diameter_other_string) = signal::<String>(String::new());
     let (weight, set_weight) = signal::<Grams>(Grams(0));
+    let (weight_kg_string, set_weight_kg_string) = signal::<String>(String::new());
+    let (weight_basis, set_weight_basis) = signal(WeightBasis::Unknown);
+    let (spool_weight, set_spool_weight) = signal::<Option<Grams>>(None);
+    let (spool_weight_kg_string, set_spool_weight_kg_string) = signal::<String>(String::new());
     let (price_dollars_string, set_price_dollars_string) = signal::<String>(String::new());
+    let (list_price_dollars_string, set_list_price_dollars_string) =
+        signal::<String>(String::new());
     let (color, set_color) = signal::<String>(String::new());
+    let (description, set_description) = signal(String::new());
+    let (nozzle_temp, set_nozzle_temp) = signal::<Option<TemperatureSpec>>(None);
+    let (bed_temp, set_bed_temp) = signal::<Option<TemperatureSpec>>(None);
+    // Set while a picker's input can't be read, so saving doesn't keep the previous temperature
+    let (nozzle_temp_invalid, set_nozzle_temp_invalid) = signal(false);
+    let (bed_temp_invalid, set_bed_temp_invalid) = signal(false);
+    let (loaded_temps, set_loaded_temps) =
+        signal::<(Option<TemperatureSpec>, Option<TemperatureSpec>)>((None, None));
+    let tags = RwSignal::new(Vec::<String>::new());
+    let ships_to = RwSignal::new(Vec::<Region>::new());
+    let (in_stock, set_in_stock) = signal::<Option<bool>>(None);
+    let (currency, set_currency) = signal(Currency::default());
     let (result_message, set_result_message) = signal::<Option<ResultMessage>>(None);
+    let (field_errors, set_field_errors) = signal::<Vec<FieldError>>(vec![]);
+
+    let field_error = move |field: ProductField| {
+        field_errors.with(|errors| {
+            errors
+                .iter()
+                .find(|e| e.field == field)
+                .map(|e| e.message.clone())
+        })
+    };
 
     let params = leptos_router::hooks::use_query_map();
     let product_query = move || params.read().get("product");
 
-    Effect::new(move |_| {
-        if let Some(product_id) = product_query() {
-            set_uuid.set(product_id.clone());
-
-            spawn_local(async move {
-                let path = format!("products/{}", product_id);
-
-                let product =
-                    request_json::<(), Product>(&path, Auth::Unauthorized, Method::GET, None).await;
-
-                match product {
-                    Ok(p) => {
-                        set_name.set(p.name);
-                        set_url.set(p.url);
-                        set_material.set(p.material);
-                        set_diameter.set(p.diameter);
-                        set_weight.set(p.weight);
-                        set_price_dollars_string.set(cents_to_dollars_string(p.price));
-                        set_color.set(p.color.to_string());
-                        set_retailer.set(p.retailer);
-                        set_retailer_pid.set(p.retailer_product_id);
-                    }
-                    Err(e) => {
-                        set_result_message.set(Some(ResultMessage::Error(format!(
-                            "Failed to load product: ({}) {}",
-                            e.status, e.message
-                        ))));
-                    }
-                }
-            });
+    // Set when the linked product is malformed or doesn't exist; the UUID is cleared so the next
+    // save creates a product rather than updating one that isn't there
+    let (product_missing, set_product_missing) = signal(false);
+
+    Effect::new(move |_| match product_query() {
+        Some(product_id) if is_uuid_shaped(&product_id) => {
+            set_product_missing.set(false);
+            set_uuid.set(product_id);
+        }
+        Some(_) => {
+            set_product_missing.set(true);
+            set_uuid.set(String::new());
         }
+        None => (),
     });
 
-    let dollars_string_to_cents = |s: String| -> Option<Cents> {
-        let s = s.trim();
+    let valid_product_query = move || product_query().filter(|id| is_uuid_shaped(id));
+
+    let loaded_product = use_resource(valid_product_query, |product_id| async move {
+        let path = format!("products/{}", product_id);
+        request_json::<(), Product>(&path, Auth::Unauthorized, Method::GET, None).await
+    });
+    // The form stays disabled until the product arrives, so nothing typed is overwritten by it
+    let loading_product = move || matches!(loaded_product.get(), LoadState::Loading);
 
-        if s.is_empty() {
-            return None;
+    Effect::new(move |_| match loaded_product.get() {
+        LoadState::Loaded(p) => {
+            set_name.set(p.name);
+            set_url.set(p.url);
+            set_material.set(p.material);
+            set_diameter_other_string.set(match p.diameter {
+                FilamentDiameter::Other(h) if h != 0 => p.diameter.mm().to_string(),
+                _ => String::new(),
+            });
+            set_diameter.set(p.diameter);
+            set_weight_kg_string.set(if p.weight.0 == 0 {
+                String::new()
+            } else {
+                p.weight.as_kg().to_string()
+            });
+            set_weight.set(p.weight);
+            set_weight_basis.set(p.weight_basis);
+            set_spool_weight_kg_string.set(
+                p.empty_spool_weight
+                    .map(|g| g.as_kg().to_string())
+                    .unwrap_or_default(),
+            );
+            set_spool_weight.set(p.empty_spool_weight);
+            set_price_dollars_string.set(p.price.to_dollars_str());
+            set_list_price_dollars_string
+                .set(p.list_price.map(Cents::to_dollars_str).unwrap_or_default());
+            set_color.set(p.color.to_string());
+            tags.set(p.tags);
+            ships_to.set(p.ships_to);
+            set_in_stock.set(p.in_stock);
+            set_currency.set(p.currency);
+            set_description.set(p.description.unwrap_or_default());
+            set_nozzle_temp.set(p.nozzle_temp);
+            set_bed_temp.set(p.bed_temp);
+            set_loaded_temps.set((p.nozzle_temp, p.bed_temp));
+            set_retailer.set(p.retailer);
+            set_pid_overridden.set(!p.retailer_product_id.is_empty());
+            set_retailer_pid.set(p.retailer_product_id);
+        }
+        LoadState::Failed(e) if e.status == 404 => {
+            set_uuid.set(String::new());
+            set_product_missing.set(true);
+        }
+        LoadState::Failed(e) => {
+            set_uuid.set(String::new());
+            set_result_message.set(Some(ResultMessage::Error(format!(
+                "Failed to load product: ({}) {}",
+                e.status, e.message
+            ))));
         }
+        LoadState::Idle | LoadState::Loading => (),
+    });
 
-        s.parse::<f32>().ok().map(Cents::from_dollars)
-    };
+    let toasts = use_toasts();
+    // Filled in once the senders below exist, so a toast for a network failure can resend the
+    // request that failed, whatever the form holds by then
+    let retry_delete = StoredValue::new(None::<Callback<String>>);
+    let retry_save = StoredValue::new(None::<Callback<SaveAttempt>>);
+    // Kept until a create gets an answer, so saving the same product again (or a retry) after a
+    // lost response can't create it twice
+    let create_key = StoredValue::new(None::<PendingCreate>);
 
-    fn cents_to_dollars_string(cents: Cents) -> String {
-        format!("{:.2}", cents.0 as f64 / 100.0)
-    }
+    let confirm_delete = RwSignal::new(false);
+    let send_delete = move |deleted: String| {
+        let path = format!("products/{}", deleted);
 
-    let on_delete = {
-        move |_| {
-            let uuid = uuid.get();
-            let path = format!("products/{}", uuid);
-
-            spawn_local(async move {
-                let res =
-                    request_json::<(), ()>(&path, Auth::Authorized, Method::DELETE, None).await;
-
-                match res {
-                    Ok(_) => {
-                        set_result_message.set(Some(ResultMessage::Success(format!(
-                            "Product with UUID {} deleted successfully",
-                            uuid
-                        ))));
+        spawn_local(async move {
+            let res = request_json::<(), ()>(&path, Auth::Authorized, Method::DELETE, None).await;
+
+            match res {
+                Ok(_) => {
+                    set_result_message.set(Some(ResultMessage::Success(format!(
+                        "Product with UUID {} deleted successfully",
+                        deleted
+                    ))));
+                    // A retry may finish after another product was loaded
+                    if uuid.get_untracked() == deleted {
                         set_uuid.set(String::new());
                     }
-                    Err(e) => {
-                        set_result_message.set(Some(ResultMessage::Error(format!(
-                            "Failed to delete product: ({}) {}",
-                            e.status, e.message
-                        ))));
+                }
+                Err(e) if e.is_network_error() => {
+                    let message = format!("Failed to delete product: {}", e.message);
+                    match retry_delete.get_value() {
+                        Some(retry) => toasts.error_with_retry(
+                            message,
+                            Callback::new(move |()| retry.run(deleted.clone())),
+                        ),
+                        None => toasts.error(message),
                     }
                 }
-            });
-        }
+                Err(e) => {
+                    set_result_message.set(Some(ResultMessage::Error(format!(
+                        "Failed to delete product: ({}) {}",
+                        e.status, e.message
+                    ))));
+                }
+            }
+        });
     };
+    retry_delete.set_value(Some(Callback::new(send_delete)));
+    let delete_product = move || send_delete(uuid.get_untracked());
+
+    let send_save = move |attempt: SaveAttempt| {
+        let SaveAttempt {
+            uuid: target,
+            product,
+            idempotency_key,
+        } = attempt.clone();
+        let path = if target.is_empty() {
+            "products".to_string()
+        } else {
+            format!("products/{target}")
+        };
+
+        spawn_local(async move {
+            let headers: Vec<(&str, &str)> = idempotency_key
+                .as_deref()
+                .map(|key| ("Idempotency-Key", key))
+                .into_iter()
+                .collect();
+            let product = request_json_with_headers::<Product, Product>(
+                &path,
+                Auth::Authorized,
+                Method::POST,
+                Some(&product),
+                &headers,
+            )
+            .await;
+
+            if !keeps_create_key(&product) {
+                create_key.update_value(|pending| {
+                    // A later save may have started another attempt since
+                    if pending.as_ref().map(|p| &p.key) == idempotency_key.as_ref() {
+                        *pending = None;
+                    }
+                });
+            }
+
+            let create_or_update_str = if target.is_empty() {
+                "create"
+            } else {
+                "update"
+            };
+
+            match product {
+                Ok(p) => {
+                    set_result_message.set(Some(ResultMessage::Success(format!(
+                        "Product \"{}\" {}d successfully with UUID {}",
+                        p.name, create_or_update_str, p.uuid
+                    ))));
+                    // A retry may finish after another product was loaded
+                    if uuid.get_untracked() == target {
+                        set_uuid.set(p.uuid);
+                    }
+                }
+                // Safe to repeat a create too, since the retry sends the same body and
+                // idempotency key
+                Err(e) if e.is_network_error() => {
+                    let message =
+                        format!("Failed to {create_or_update_str} product: {}", e.message);
+                    match retry_save.get_value() {
+                        Some(retry) => toasts.error_with_retry(
+                            message,
+                            Callback::new(move |()| retry.run(attempt.clone())),
+                        ),
+                        None => toasts.error(message),
+                    }
+                }
+                Err(e) => {
+                    // Backend validation failures go beside their inputs; fields the editor
+                    // has no input for stay in the banner
+                    let mut placed = vec![];
+                    let mut details = String::new();
+
+                    for (name, message) in &e.fields {
+                        match ProductField::from_api_name(name) {
+                            Some(field) => placed.push(FieldError {
+                                field,
+                                message: message.clone(),
+                            }),
+                            None => details.push_str(&format!(" {name}: {message}.")),
+                        }
+                    }
+
+                    set_field_errors.set(placed);
+                    set_result_message.set(Some(ResultMessage::Error(format!(
+                        "Failed to {} product: ({}) {}{details}",
+                        create_or_update_str, e.status, e.message
+                    ))));
+                }
+            }
+        });
+    };
+    retry_save.set_value(Some(Callback::new(send_save)));
+
+    let save_product = {
+        move || {
+            let normalized_name = normalize_name(&name.get());
+            let normalized_url = normalize_url(&url.get(), &retailer.get());
+            set_name.set(normalized_name.clone());
+            set_url.set(normalized_url.clone());
 
-    let on_update = {
-        move |_| {
             let product = Product {
                 uuid: String::new(),
-                name: name.get().trim().to_string(),
-                price: dollars_string_to_cents(price_dollars_string.get()).unwrap_or(Cents(0)),
+                name: normalized_name,
+                price: Cents::from_dollars_str(&price_dollars_string.get()).unwrap_or(Cents(0)),
+                list_price: Cents::from_dollars_str(&list_price_dollars_string.get()),
                 price_per_kg: Cents(0), // to be calculated server-side
-                url: url.get(),
-                material: material.get(),
+                currency: currency.get(),
+                url: normalized_url,
+                material: match material.get() {
+                    FilamentMaterial::Other(s) => FilamentMaterial::Other(clamp_other(&s)),
+                    m => m,
+                },
                 diameter: diameter.get(),
                 weight: weight.get(),
                 retailer: retailer.get(),
                 retailer_product_id: retailer_pid.get().trim().to_string(),
-                color: FilamentColor::from_str(color.get().trim()).unwrap_or_default(),
+                color: FilamentColor::from_str(&clamp_other(&color.get())).unwrap_or_default(),
+                weight_basis: weight_basis.get(),
+                empty_spool_weight: match weight_basis.get() {
+                    WeightBasis::GrossWithSpool => spool_weight.get(),
+                    _ => None,
+                },
+                in_stock: in_stock.get(),
+                ships_to: ships_to.get(),
+                tags: tags.get(),
+                description: Some(description.get().trim().to_string()).filter(|d| !d.is_empty()),
+                nozzle_temp: nozzle_temp.get(),
+                bed_temp: bed_temp.get(),
+                updated_at: None,
+                updated_by: None,
+                price_updated_at: None,
             };
 
-            enum ProductAction {
-                Create,
-                Update,
+            if let Err(errors) = product.validate() {
+                set_field_errors.set(errors);
+                set_result_message.set(Some(ResultMessage::Error(
+                    "Fix the highlighted fields before saving".to_string(),
+                )));
+                return;
             }
+            set_field_errors.set(vec![]);
 
-            let action = if uuid.get().is_empty() {
-                ProductAction::Create
-            } else {
-                ProductAction::Update
-            };
+            if nozzle_temp_invalid.get() || bed_temp_invalid.get() {
+                set_result_message.set(Some(ResultMessage::Error(
+                    "Fix the temperatures before saving".to_string(),
+                )));
+                return;
+            }
 
-            let path = if let ProductAction::Create = action {
-                "products".to_string()
-            } else {
-                format!("products/{}", uuid.get())
-            };
+            let target = uuid.get();
+            let idempotency_key = target.is_empty().then(|| {
+                let body = serde_json::to_string(&product).unwrap_or_default();
+                create_key
+                    .try_update_value(|pending| {
+                        create_attempt_key(pending, body, new_idempotency_key)
+                    })
+                    .unwrap_or_else(new_idempotency_key)
+            });
 
-            spawn_local(async move {
-                let product = request_json::<Product, Product>(
-                    &path,
-                    Auth::Authorized,
-                    Method::POST,
-                    Some(&product),
-                )
-                .await;
-
-                let create_or_update_str = if let ProductAction::Create = action {
-                    "create"
-                } else {
-                    "update"
-                };
-
-                match product {
-                    Ok(p) => {
-                        set_result_message.set(Some(ResultMessage::Success(format!(
-                            "Product \"{}\" {}d successfully with UUID {}",
-                            p.name, create_or_update_str, p.uuid
-                        ))));
-                        set_uuid.set(p.uuid);
-                    }
-                    Err(e) => {
-                        set_result_message.set(Some(ResultMessage::Error(format!(
-                            "Failed to {} product: ({}) {}",
-                            create_or_update_str, e.status, e.message
-                        ))));
-                    }
-                }
+            send_save(SaveAttempt {
+                uuid: target,
+                product,
+                idempotency_key,
             });
         }
     };
 
+    let taxonomy = use_taxonomy();
+    // Materials the server's taxonomy adds arrive as `Other` but get their own option
+    let is_listed_material = move |m: &FilamentMaterial| taxonomy.with(|t| t.materials.contains(m));
+
     let mat_select_value = move || match material.get() {
+        m if is_listed_material(&m) => m.to_string(),
         FilamentMaterial::Other(_) => "Other".to_string(),
         m => m.to_string(),
     };
 
     let mat_other_value = move || match material.get() {
+        m if is_listed_material(&m) => String::new(),
         FilamentMaterial::Other(s) => s,
         _ => String::new(),
     };
 
+    let mat_other_input = NodeRef::<html::Input>::new();
+    let diam_other_input = NodeRef::<html::Input>::new();
+    let retailer_other_input = NodeRef::<html::Input>::new();
+    let color_input = NodeRef::<html::Input>::new();
+    let navigate = leptos_router::hooks::use_navigate();
+
+    // Clears every field for a product entered from scratch
+    let start_new_product = {
+        let navigate = navigate.clone();
+
+        move |_| {
+            set_uuid.set(String::new());
+            create_key.set_value(None);
+            set_name.set(String::new());
+            set_url.set(String::new());
+            set_material.set(FilamentMaterial::Unspecified);
+            set_retailer.set(Retailer::Other(String::new()));
+            set_retailer_pid.set(String::new());
+            set_pid_overridden.set(false);
+            set_diameter.set(FilamentDiameter::Unspecified);
+            set_diameter_other_string.set(String::new());
+            set_weight.set(Grams(0));
+            set_weight_kg_string.set(String::new());
+            set_weight_basis.set(WeightBasis::Unknown);
+            set_spool_weight.set(None);
+            set_spool_weight_kg_string.set(String::new());
+            set_price_dollars_string.set(String::new());
+            set_list_price_dollars_string.set(String::new());
+            set_color.set(String::new());
+            set_description.set(String::new());
+            set_nozzle_temp.set(None);
+            set_bed_temp.set(None);
+            set_loaded_temps.set((None, None));
+            tags.set(vec![]);
+            ships_to.set(vec![]);
+            set_in_stock.set(None);
+            set_currency.set(Currency::default());
+            set_result_message.set(None);
+            set_field_errors.set(vec![]);
+            set_product_missing.set(false);
+
+            navigate(
+                "/admin",
+                leptos_router::NavigateOptions {
+                    replace: true,
+                    ..Default::default()
+                },
+            );
+        }
+    };
+
+    // Keeps every field but forgets the UUID, so the next save creates a new product. Typical
+    // use is adding the other colors of a product line.
+    let duplicate_product = move |_| {
+        set_uuid.set(String::new());
+        create_key.set_value(None);
+        set_field_errors.set(vec![]);
+        set_result_message.set(Some(ResultMessage::Success(
+            "Editing a copy; saving will create a new product".to_string(),
+        )));
+
+        // Otherwise reloading the page would open the original again
+        if product_query().is_some() {
+            navigate(
+                "/admin",
+                leptos_router::NavigateOptions {
+                    replace: true,
+                    ..Default::default()
+                },
+            );
+        }
+
+        focus_when_shown(color_input);
+    };
+
+    let diam_select_value = move || match diameter.get() {
+        FilamentDiameter::Other(_) => "Other".to_string(),
+        FilamentDiameter::Unspecified => "Unspecified".to_string(),
+        d => d.mm().to_string(),
+    };
+
     let plat_select_value = move || match retailer.get() {
         Retailer::Other(_) => "Other".to_string(),
         m => m.to_string(),
@@ -224,14 +589,40 @@ pub fn ProductEditor(product_id: Option<String>) -> impl IntoView {
         _ => String::new(),
     };
 
+    let autofill_from_url = move |url: &str| {
+        if pid_overridden.get_untracked() {
+            return;
+        }
+
+        let retailer = match retailer.get_untracked() {
+            Retailer::Other(s) if s.trim().is_empty() => {
+                let detected = Retailer::from_url(url);
+
+                if let Some(r) = &detected {
+                    set_retailer.set(r.clone());
+                }
+
+                detected
+            }
+            r => Some(r),
+        };
+
+        set_retailer_pid.set(
+            retailer
+                .and_then(|r| r.product_id_from_url(url))
+                .unwrap_or_default(),
+        );
+    };
+
     let all_colors = crate::product::FilamentColor::iter().collect::<Vec<_>>();
 
     view! {
-        <h2>"Filament color text demos"</h2>
+        <h2>"Filament color demos"</h2>
         <div style="display: flex; gap: 8px; flex-wrap: wrap; margin-bottom: 16px;">
             { all_colors.iter().map(|c| {
                 view! {
-                    <div style={format!("color: {};", c.hex())}>
+                    <div>
+                        <ColorChip color=c.clone() />
                         {c.to_string()}
                     </div>
                 }
@@ -239,251 +630,978 @@ pub fn ProductEditor(product_id: Option<String>) -> impl IntoView {
         </div>
         <div class="container full-width">
             <h2>"Create/Update Product"</h2>
-            <section style="display: grid; gap: 12px;">
-                <div>
-                    <label>"Product UUID"</label>
-                    <input
-                        class="input"
-                        type="text"
-                        placeholder="(leave blank to create new)"
-                        prop:value=move || uuid.get()
-                        on:input=move |e| set_uuid.set(event_target_value(&e))
-                    />
-                </div>
-                <div>
-                    <label>"Product Name"</label>
-                    <input
-                        class="input"
-                        type="text"
-                        placeholder="Product name"
-                        prop:value=move || name.get()
-                        on:input=move |e| set_name.set(event_target_value(&e))
-                    />
+            <Show when=move || product_missing.get()>
+                <div class="options-row" role="alert">
+                    <p class="error">"No product found for this ID"</p>
+                    <button on:click=start_new_product.clone()>"Start a new product"</button>
                 </div>
-                <div class="options-row">
+            </Show>
+            <Show when=loading_product>
+                <div class="loading" role="status">"Loading product…"</div>
+            </Show>
+            <fieldset
+                class="editor-fields"
+                prop:disabled=loading_product
+                aria-busy=move || loading_product().to_string()
+            >
+                <section style="display: grid; gap: 12px;">
                     <div>
-                        <label>"Price"</label>
+                        <label>"Product UUID"</label>
                         <input
                             class="input"
                             type="text"
-                            placeholder="In USD (e.g. 19.99)"
-                            prop:value=move || price_dollars_string.get()
-                            on:input=move |e| set_price_dollars_string.set(event_target_value(&e))
+                            placeholder="(leave blank to create new)"
+                            prop:value=move || uuid.get()
+                            on:input=move |e| set_uuid.set(event_target_value(&e))
                         />
                     </div>
                     <div>
-                        <label>"Material"</label>
-                        <select
+                        <label>"Product Name"</label>
+                        <input
                             class="input"
-                            prop:value=mat_select_value
-                            on:change=move |e| {
-                                let v = event_target_value(&e);
+                            type="text"
+                            placeholder="Product name"
+                            prop:value=move || name.get()
+                            on:input=move |e| set_name.set(event_target_value(&e))
+                        />
+                        {move || field_error(ProductField::Name).map(|e| view! { <small class="error">{e}</small> })}
+                    </div>
+                    <div class="options-row">
+                        <div>
+                            <label>"Price"</label>
+                            <input
+                                class="input"
+                                type="text"
+                                placeholder=move || format!("In {} (e.g. 19.99)", currency.get())
+                                prop:value=move || price_dollars_string.get()
+                                on:input=move |e| set_price_dollars_string.set(event_target_value(&e))
+                            />
+                            {move || field_error(ProductField::Price).map(|e| view! { <small class="error">{e}</small> })}
+                        </div>
+                        <div>
+                            <label>"Currency"</label>
+                            <select
+                                class="input"
+                                prop:value=move || currency.get().code()
+                                on:change=move |e| {
+                                    if let Ok(c) = event_target_value(&e).parse() {
+                                        set_currency.set(c);
+                                    }
+                                }
+                            >
+                                {Currency::iter()
+                                    .map(|c| view! { <option value=c.code()>{c.to_string()}</option> })
+                                    .collect_view()}
+                            </select>
+                        </div>
+                        <div>
+                            <label>"List Price"</label>
+                            <input
+                                class="input"
+                                type="text"
+                                placeholder="Regular price, if on sale"
+                                prop:value=move || list_price_dollars_string.get()
+                                on:input=move |e| set_list_price_dollars_string.set(event_target_value(&e))
+                            />
+                        </div>
+                        <div>
+                            <label>"Material"</label>
+                            <select
+                                class="input"
+                                prop:value=mat_select_value
+                                on:change=move |e| {
+                                    let v = event_target_value(&e);
+
+                                    if v == "Other" {
+                                        set_material.update(|m| if !matches!(m, FilamentMaterial::Other(_)) {
+                                        *m = FilamentMaterial::Other(String::new());
+                                        });
+                                        focus_when_shown(mat_other_input);
+                                        return;
+                                    }
 
-                                if v == "Other" {
-                                    set_material.update(|m| if !matches!(m, FilamentMaterial::Other(_)) {
-                                    *m = FilamentMaterial::Other(String::new());
+                                    let listed = taxonomy.with_untracked(|t| {
+                                        t.materials.iter().find(|m| m.to_string() == v).cloned()
                                     });
-                                    return;
+
+                                    match listed {
+                                        Some(m) => set_material.set(m),
+                                        None if v == "Unspecified" => set_material.set(FilamentMaterial::Unspecified),
+                                        None => set_material.set(FilamentMaterial::Other(v)),
+                                    }
                                 }
+                            >
+                                {move || {
+                                    taxonomy.with(|t| {
+                                        t.materials
+                                            .iter()
+                                            .map(|m| {
+                                                let label = m.to_string();
+                                                view! { <option value=label.clone()>{ label.clone() }</option> }
+                                            })
+                                            .collect_view()
+                                    })
+                                }}
+                                <option value="Unspecified">"Unspecified"</option>
+                                <option value="Other">"Other…"</option>
+                            </select>
 
-                                if let Some(m) = FilamentMaterial::iter()
-                                    .filter(|m| !matches!(m, FilamentMaterial::Other(_)))
-                                    .find(|m| m.to_string() == v)
-                                {
-                                    set_material.set(m.clone());
-                                } else {
-                                    set_material.set(FilamentMaterial::Other(v));
+                            <Show when=move || matches!(material.get(), FilamentMaterial::Other(_))>
+                                <input
+                                class="input"
+                                type="text"
+                                placeholder="Material name"
+                                aria-label="Material name"
+                                maxlength=MAX_OTHER_LEN
+                                node_ref=mat_other_input
+                                prop:value=mat_other_value
+                                on:input=move |e| {
+                                    set_material.set(FilamentMaterial::Other(event_target_value(&e)));
                                 }
-                            }
-                        >
-                            {
-                                FilamentMaterial::iter()
-                                    .filter(|m| !matches!(m, FilamentMaterial::Other(_)))
+                                />
+                                {move || match material.get() {
+                                    FilamentMaterial::Other(s) => other_length_hint(&s)
+                                        .map(|h| view! { <small class="hint">{h}</small> }),
+                                    _ => None,
+                                }}
+                            </Show>
+                            {move || field_error(ProductField::Material).map(|e| view! { <small class="error">{e}</small> })}
+                        </div>
+                        <div>
+                            <label>"Color"</label>
+                            <input
+                                class="input"
+                                type="text"
+                                placeholder="Color name"
+                                list="known-colors"
+                                maxlength=MAX_OTHER_LEN
+                                node_ref=color_input
+                                prop:value=move || color.get()
+                                on:input=move |e| set_color.set(event_target_value(&e))
+                            />
+                            {move || other_length_hint(&color.get()).map(|h| view! { <small class="hint">{h}</small> })}
+                            {move || field_error(ProductField::Color).map(|e| view! { <small class="error">{e}</small> })}
+                            <datalist id="known-colors">
+                                {move || {
+                                    taxonomy.with(|t| {
+                                        t.colors
+                                            .iter()
+                                            .map(|c| view! { <option value=c.to_string()></option> })
+                                            .collect_view()
+                                    })
+                                }}
+                            </datalist>
+                            <ColorSwatchPicker
+                                colors=Signal::derive(move || taxonomy.with(|t| t.colors.clone()))
+                                selected=Signal::derive(move || FilamentColor::from_str(color.get().trim()).ok())
+                                on_select=Callback::new(move |c: FilamentColor| set_color.set(c.to_string()))
+                            />
+                        </div>
+                        <div>
+                            <label>"Diameter"</label>
+                            <select
+                                class="input"
+                                prop:value=diam_select_value
+                                on:change=move |e| {
+                                    let v = event_target_value(&e);
+                                    set_diameter.update(|d| *d = diameter_from_select(&v, *d));
+
+                                    if v == "Other" {
+                                        focus_when_shown(diam_other_input);
+                                    }
+                                }
+                            >
+                                {
+                                    FilamentDiameter::iter()
+                                    .filter(|m| {
+                                        !matches!(m, FilamentDiameter::Other(_) | FilamentDiameter::Unspecified)
+                                    })
                                     .map(|m| {
-                                        let label = m.to_string();
-                                        view! { <option value=label.clone()>{ label.clone() }</option> }
+                                        let label = m.mm();
+                                        view! { <option value=label>{ label }</option> }
                                     })
                                     .collect_view()
-                            }
-                            <option value="Other">"Other…"</option>
-                        </select>
-
-                        <Show when=move || matches!(material.get(), FilamentMaterial::Other(_))>
+                                }
+                                <option value="Other">"Other…"</option>
+                                <option value="Unspecified">"Unspecified"</option>
+                            </select>
+                            <Show when=move || matches!(diameter.get(), FilamentDiameter::Other(_))>
+                                <input
+                                    class="input"
+                                    type="number"
+                                    inputmode="numeric"
+                                    placeholder="In mm (e.g. 1.75)"
+                                    aria-label="Diameter in mm"
+                                    node_ref=diam_other_input
+                                    prop:value=move || diameter_other_string.get()
+                                    on:input=move |e| {
+                                        let v = event_target_value(&e);
+                                        // Stay on "Other" while the box is empty or unparseable
+                                        set_diameter.set(match FilamentDiameter::from_mm_string(&v) {
+                                            FilamentDiameter::Unspecified => FilamentDiameter::Other(0),
+                                            d => d,
+                                        });
+                                        set_diameter_other_string.set(v);
+                                    }
+                                />
+                                {move || {
+                                    diameter_other_string
+                                        .with(|v| custom_diameter_warning(v))
+                                        .map(|(warning, suggestion)| {
+                                            view! {
+                                                <small class="price-note">
+                                                    {warning}
+                                                    {suggestion.map(|mm| {
+                                                        let label = format!(" Use {mm} mm?");
+                                                        view! {
+                                                            <button
+                                                                class="link-button"
+                                                                on:click=move |_| {
+                                                                    set_diameter.set(FilamentDiameter::from_mm_string(&mm));
+                                                                    set_diameter_other_string.set(mm.clone());
+                                                                }
+                                                            >
+                                                                {label}
+                                                            </button>
+                                                        }
+                                                    })}
+                                                </small>
+                                            }
+                                        })
+                                }}
+                            </Show>
+                            {move || field_error(ProductField::Diameter).map(|e| view! { <small class="error">{e}</small> })}
+                        </div>
+                        <div>
+                            <label>"Spool Weight"</label>
                             <input
-                            class="input"
-                            type="text"
-                            placeholder="Material name"
-                            prop:value=mat_other_value
-                            on:input=move |e| {
-                                set_material.set(FilamentMaterial::Other(event_target_value(&e)));
-                            }
+                                class="input"
+                                type="number"
+                                inputmode="numeric"
+                                placeholder="In kg (e.g. 1.25)"
+                                aria-label="Spool weight in kg"
+                                prop:value=move || weight_kg_string.get()
+                                on:input=move |e| {
+                                    let v = event_target_value(&e);
+                                    set_weight.set(Grams::from_kg_string(&v));
+                                    set_weight_kg_string.set(v);
+                                }
                             />
-                        </Show>
-                    </div>
-                    <div>
-                        <label>"Color"</label>
-                        <input
-                            class="input"
-                            type="text"
-                            placeholder="Color name"
-                            prop:value=move || color.get()
-                            on:input=move |e| set_color.set(event_target_value(&e))
-                        />
-                    </div>
-                    <div>
-                        <label>"Diameter"</label>
-                        <select
-                            class="input"
-                            on:change=move |e| {
-                                let v = event_target_value(&e);
-                                let all_diameters: Vec<_> = FilamentDiameter::iter().collect();
+                            {move || field_error(ProductField::Weight).map(|e| view! { <small class="error">{e}</small> })}
+                        </div>
+                        <div>
+                            <label for="weight-basis">"Weight Basis"</label>
+                            <select
+                                id="weight-basis"
+                                class="input"
+                                prop:value=move || weight_basis.get().code()
+                                on:change=move |e| set_weight_basis.set(WeightBasis::from_code(&event_target_value(&e)))
+                            >
+                                {WeightBasis::iter()
+                                    .map(|b| view! { <option value=b.code()>{b.to_string()}</option> })
+                                    .collect_view()}
+                            </select>
+                            <Show when=move || weight_basis.get() == WeightBasis::GrossWithSpool>
+                                <input
+                                    class="input"
+                                    type="number"
+                                    inputmode="numeric"
+                                    placeholder="Empty spool in kg (e.g. 0.25)"
+                                    aria-label="Empty spool weight in kg"
+                                    prop:value=move || spool_weight_kg_string.get()
+                                    on:input=move |e| {
+                                        let v = event_target_value(&e);
+                                        let grams = Grams::from_kg_string(&v);
+                                        set_spool_weight.set((grams.0 != 0).then_some(grams));
+                                        set_spool_weight_kg_string.set(v);
+                                    }
+                                />
+                            </Show>
+                        </div>
+                        <div>
+                            <label>"Retailer"</label>
+                            <select
+                                class="input"
+                                prop:value=plat_select_value
+                                on:change=move |e| {
+                                    let v = event_target_value(&e);
 
-                                for d in &all_diameters {
-                                    if let FilamentDiameter::Other(_) = d {
-                                        continue;
+                                    if v == "Other" {
+                                        set_retailer.update(|m| if !matches!(m, Retailer::Other(_)) {
+                                        *m = Retailer::Other(String::new());
+                                        });
+                                        focus_when_shown(retailer_other_input);
+                                        return;
                                     }
 
-                                    if d.mm().to_string() == v {
-                                        set_diameter.set(*d);
-                                        break;
+                                    if let Some(m) = Retailer::iter()
+                                        .filter(|m| !matches!(m, Retailer::Other(_)))
+                                        .find(|m| m.to_string() == v)
+                                    {
+                                        set_retailer.set(m.clone());
                                     } else {
-                                        let v = v.parse::<f32>().unwrap_or(0.0);
-                                        let v = (v * 100.0).round() as u16;
-                                        set_diameter.set(FilamentDiameter::Other(v));
+                                        set_retailer.set(Retailer::Other(v));
                                     }
                                 }
-                            }
-                        >
-                            {
-                                FilamentDiameter::iter()
-                                .filter(|m| !matches!(m, FilamentDiameter::Other(_)))
-                                .map(|m| {
-                                    let label = m.mm();
-                                    view! { <option value=label>{ label }</option> }
-                                })
-                                .collect_view()
-                            }
-                            <option value="Other">"Other…"</option>
-                        </select>
-                        <Show when=move || matches!(diameter.get(), FilamentDiameter::Other(_))>
+                            >
+                                {
+                                    Retailer::iter()
+                                        .filter(|m| !matches!(m, Retailer::Other(_)))
+                                        .map(|m| {
+                                            let label = m.to_string();
+                                            view! { <option value=label.clone()>{ label.clone() }</option> }
+                                        })
+                                        .collect_view()
+                                }
+                                <option value="Other">"Other…"</option>
+                            </select>
+
+                            <Show when=move || matches!(retailer.get(), Retailer::Other(_))>
+                                <input
+                                class="input"
+                                type="text"
+                                placeholder="Retailer name"
+                                aria-label="Retailer name"
+                                node_ref=retailer_other_input
+                                prop:value=plat_other_value
+                                on:input=move |e| {
+                                    set_retailer.set(Retailer::Other(event_target_value(&e)));
+                                }
+                                />
+                            </Show>
+                        </div>
+                        <div>
+                            <label>"Retailer Product ID"</label>
                             <input
                                 class="input"
-                                type="number"
-                                inputmode="numeric"
-                                placeholder="In mm (e.g. 1.75)"
+                                type="text"
+                                placeholder="SKU/ASIN"
+                                prop:value=move || retailer_pid.get()
                                 on:input=move |e| {
-                                    set_diameter.update(|df| {
-                                        *df = FilamentDiameter::from_mm_string(&event_target_value(&e));
-                                    });
+                                    let v = event_target_value(&e);
+                                    set_pid_overridden.set(!v.trim().is_empty());
+                                    set_retailer_pid.set(v);
                                 }
                             />
-                        </Show>
+                            <Show when=move || !pid_overridden.get() && !retailer_pid.get().is_empty()>
+                                <small class="hint">"Filled in from the URL"</small>
+                            </Show>
+                        </div>
                     </div>
                     <div>
-                        <label>"Spool Weight"</label>
-                        <input
-                            class="input"
-                            type="number"
-                            inputmode="numeric"
-                            placeholder="In kg (e.g. 1.25)"
-                            on:input=move |e| {
-                                set_weight.update(|v| {
-                                    let g = (event_target_value(&e).parse::<f32>().unwrap_or(0.0) * 1000.0).round() as u16;
-                                    *v = Grams(g);
-                                });
-                            }
-                        />
-                    </div>
-                    <div>
-                        <label>"Retailer"</label>
+                        <label for="in-stock">"Availability"</label>
                         <select
+                            id="in-stock"
                             class="input"
-                            prop:value=plat_select_value
+                            prop:value=move || match in_stock.get() {
+                                Some(true) => "in",
+                                Some(false) => "out",
+                                None => "unknown",
+                            }
                             on:change=move |e| {
-                                let v = event_target_value(&e);
-
-                                if v == "Other" {
-                                    set_retailer.update(|m| if !matches!(m, Retailer::Other(_)) {
-                                    *m = Retailer::Other(String::new());
-                                    });
-                                    return;
-                                }
-
-                                if let Some(m) = Retailer::iter()
-                                    .filter(|m| !matches!(m, Retailer::Other(_)))
-                                    .find(|m| m.to_string() == v)
-                                {
-                                    set_retailer.set(m.clone());
-                                } else {
-                                    set_retailer.set(Retailer::Other(v));
-                                }
+                                set_in_stock.set(match event_target_value(&e).as_str() {
+                                    "in" => Some(true),
+                                    "out" => Some(false),
+                                    _ => None,
+                                })
                             }
                         >
-                            {
-                                Retailer::iter()
-                                    .filter(|m| !matches!(m, Retailer::Other(_)))
-                                    .map(|m| {
-                                        let label = m.to_string();
-                                        view! { <option value=label.clone()>{ label.clone() }</option> }
-                                    })
-                                    .collect_view()
-                            }
-                            <option value="Other">"Other…"</option>
+                            <option value="unknown">"Unknown"</option>
+                            <option value="in">"In stock"</option>
+                            <option value="out">"Out of stock"</option>
                         </select>
-
-                        <Show when=move || matches!(retailer.get(), Retailer::Other(_))>
-                            <input
+                    </div>
+                    <div>
+                        <label>"Ships To"</label>
+                        <div class="checkbox-label">
+                            {Region::iter()
+                                .map(|r| {
+                                    view! {
+                                        <label class="checkbox-label">
+                                            <input
+                                                type="checkbox"
+                                                prop:checked=move || ships_to.with(|s| s.contains(&r))
+                                                on:change=move |e| {
+                                                    let checked = event_target_checked(&e);
+                                                    ships_to.update(|s| {
+                                                        s.retain(|x| *x != r);
+                                                        if checked {
+                                                            s.push(r);
+                                                        }
+                                                    });
+                                                }
+                                            />
+                                            {r.code()}
+                                        </label>
+                                    }
+                                })
+                                .collect_view()}
+                        </div>
+                        <small class="hint">"Leave all unchecked if unknown"</small>
+                    </div>
+                    <div>
+                        <label>"Tags"</label>
+                        <TagInput tags=tags set_tags=tags placeholder="e.g. silk, refill (Enter or comma to add)" />
+                    </div>
+                    <div>
+                        <label>"Description"</label>
+                        <textarea
                             class="input"
-                            type="text"
-                            placeholder="Retailer name"
-                            prop:value=plat_other_value
-                            on:input=move |e| {
-                                set_retailer.set(Retailer::Other(event_target_value(&e)));
-                            }
-                            />
-                        </Show>
+                            rows="3"
+                            placeholder="e.g. high-flow, matte finish"
+                            maxlength=MAX_DESCRIPTION_LEN
+                            prop:value=move || description.get()
+                            on:input=move |e| set_description.set(event_target_value(&e))
+                        ></textarea>
+                        <small class="hint">
+                            {move || format!("{}/{MAX_DESCRIPTION_LEN} characters", description.with(|d| d.chars().count()))}
+                        </small>
+                        {move || field_error(ProductField::Description).map(|e| view! { <small class="error">{e}</small> })}
                     </div>
+                    // Re-created whenever a product loads so the pickers start from its temperatures
+                    {move || {
+                        let (nozzle, bed) = loaded_temps.get();
+                        view! {
+                            <div class="options-row">
+                                <TemperaturePicker
+                                    id="nozzle-temp"
+                                    label="Nozzle Temperature"
+                                    initial=nozzle
+                                    on_change=move |t| set_nozzle_temp.set(t)
+                                    set_invalid=set_nozzle_temp_invalid
+                                />
+                                <TemperaturePicker
+                                    id="bed-temp"
+                                    label="Bed Temperature"
+                                    initial=bed
+                                    on_change=move |t| set_bed_temp.set(t)
+                                    set_invalid=set_bed_temp_invalid
+                                />
+                            </div>
+                        }
+                    }}
                     <div>
-                        <label>"Retailer Product ID"</label>
+                        <label>"Product Page URL"</label>
                         <input
                             class="input"
                             type="text"
-                            placeholder="SKU/ASIN"
-                            prop:value=move || retailer_pid.get()
-                            on:input=move |e| set_retailer_pid.set(event_target_value(&e))
+                            placeholder="https://example.com/product-page"
+                            prop:value=move || url.get()
+                            on:input=move |e| {
+                                let v = event_target_value(&e);
+                                autofill_from_url(&v);
+                                set_url.set(v);
+                            }
                         />
+                        {move || field_error(ProductField::Url).map(|e| view! { <small class="error">{e}</small> })}
                     </div>
-                </div>
-                <div>
-                    <label>"Product Page URL"</label>
-                    <input
-                        class="input"
-                        type="text"
-                        placeholder="https://example.com/product-page"
-                        on:input=move |e| set_url.set(event_target_value(&e))
-                    />
-                </div>
-                <div class="options-row">
-                    <button on:click=on_update>
-                        {
-                            move || if uuid.get().is_empty() {
-                                "Create Product"
-                            } else {
-                                "Update Product"
+                    <div class="options-row">
+                        <button on:click=move |_| save_product()>
+                            {
+                                move || if uuid.get().is_empty() {
+                                    "Create Product"
+                                } else {
+                                    "Update Product"
+                                }
                             }
-                        }
-                    </button>
-                    <Show when=move || !uuid.get().is_empty()>
-                        <button class="danger" on:click=on_delete>"Delete Product"</button>
+                        </button>
+                        <Show when=move || !uuid.get().is_empty()>
+                            <button on:click=duplicate_product.clone()>"Duplicate"</button>
+                            <button class="danger" on:click=move |_| confirm_delete.set(true)>"Delete Product"</button>
+                        </Show>
+                    </div>
+                    <Show when=move || result_message.get().is_some()>
+                        {move || match result_message.get().unwrap() {
+                            ResultMessage::Success(s) => view! { <p class="success">{s}</p> }.into_view(),
+                            ResultMessage::Error(s)   => view! { <p class="error">{s}</p> }.into_view(),
+                        }}
                     </Show>
+                    <TypedConfirm
+                        open=confirm_delete
+                        title="Delete product"
+                        message=Signal::derive(move || {
+                            format!("This permanently deletes \"{}\".", name.get())
+                        })
+                        expected=name
+                        action_label="Delete Product"
+                        on_confirm=delete_product
+                    />
+                </section>
+            </fieldset>
+        </div>
+    }
+}
+
+/// A warning for a custom diameter outside the range [`Product::validate`] accepts, plus a
+/// corrected value in mm when it looks like hundredths or tenths were typed (175 or 17.5 for 1.75)
+fn custom_diameter_warning(input: &str) -> Option<(String, Option<String>)> {
+    let mm: f32 = input.trim().replace(',', ".").parse().ok()?;
+    let (min_mm, max_mm) = (MIN_DIAMETER_H as f32 / 100.0, MAX_DIAMETER_H as f32 / 100.0);
+    // Compared in hundredths, as saving rounds to them
+    let plausible =
+        |mm: f32| (MIN_DIAMETER_H..=MAX_DIAMETER_H).contains(&((mm * 100.0).round() as u16));
+
+    if plausible(mm) {
+        return None;
+    }
+
+    let suggestion = [100.0, 10.0]
+        .into_iter()
+        .map(|divisor| mm / divisor)
+        .find(|&corrected| plausible(corrected))
+        .map(|corrected| format!("{corrected:.2}"));
+
+    Some((
+        format!("{mm} mm is outside {min_mm}–{max_mm} mm."),
+        suggestion,
+    ))
+}
+
+/// One request to save the editor's product, kept so a retry resends it unchanged
+#[derive(Clone)]
+struct SaveAttempt {
+    /// The product to update, or empty to create one
+    uuid: String,
+    product: Product,
+    idempotency_key: Option<String>,
+}
+
+/// A create whose response was lost, or that is still in flight
+#[derive(Clone)]
+struct PendingCreate {
+    key: String,
+    /// The serialized product the key was sent with
+    body: String,
+}
+
+/// The idempotency key for creating the product serialized as `body`: the pending one when an
+/// earlier attempt sent the same body and its response was lost, otherwise a new one that stays
+/// pending until [`keeps_create_key`] says otherwise. A changed body is a different create, and
+/// the server would replay or reject it under the old key.
+fn create_attempt_key(
+    pending: &mut Option<PendingCreate>,
+    body: String,
+    new_key: impl FnOnce() -> String,
+) -> String {
+    match pending {
+        Some(p) if p.body == body => p.key.clone(),
+        _ => pending
+            .insert(PendingCreate {
+                key: new_key(),
+                body,
+            })
+            .key
+            .clone(),
+    }
+}
+
+/// Whether a create's key should be sent again with the next attempt. Only a lost response keeps
+/// it, since the server may have created the product; anything else ends the attempt.
+fn keeps_create_key<T>(response: &Result<T, ErrorResponse>) -> bool {
+    response.as_ref().is_err_and(|e| e.is_network_error())
+}
+
+/// Whether `id` has the 8-4-4-4-12 hex digit shape of a UUID, so malformed links aren't fetched
+fn is_uuid_shaped(id: &str) -> bool {
+    let groups: Vec<&str> = id.split('-').collect();
+
+    groups.len() == 5
+        && groups
+            .iter()
+            .zip([8, 4, 4, 4, 12])
+            .all(|(g, len)| g.len() == len && g.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// The diameter chosen in the editor's select: a preset by its mm value, `Unspecified`, or `Other`
+/// (keeping any custom value already entered) when "Other" is picked. Anything else leaves `current` alone.
+fn diameter_from_select(value: &str, current: FilamentDiameter) -> FilamentDiameter {
+    match value {
+        "Other" => {
+            return match current {
+                FilamentDiameter::Other(_) => current,
+                _ => FilamentDiameter::Other(0),
+            };
+        }
+        "Unspecified" => return FilamentDiameter::Unspecified,
+        _ => {}
+    }
+
+    FilamentDiameter::iter()
+        .filter(|d| {
+            !matches!(
+                d,
+                FilamentDiameter::Other(_) | FilamentDiameter::Unspecified
+            )
+        })
+        .find(|d| d.mm().to_string() == value)
+        .unwrap_or(current)
+}
+
+/// Highest temperature the picker accepts, in °C
+const MAX_TEMP_C: u16 = 500;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum TemperatureMode {
+    Unspecified,
+    Exact,
+    Range,
+}
+
+fn parse_temp(s: &str) -> Result<u16, String> {
+    let s = s.trim();
+
+    if s.is_empty() {
+        return Err("Enter a temperature".to_string());
+    }
+
+    match s.parse::<u16>() {
+        Ok(t) if t <= MAX_TEMP_C => Ok(t),
+        Ok(_) => Err(format!("Must be between 0 and {MAX_TEMP_C} °C")),
+        Err(_) => Err("Enter a whole number of °C".to_string()),
+    }
+}
+
+/// Lets an admin enter a temperature as a single value or a range.
+/// `on_change` receives `None` only when the temperature is left unspecified; while the input is
+/// invalid nothing is emitted, the problem is shown instead, and `set_invalid` is set.
+#[component]
+fn TemperaturePicker<F>(
+    id: &'static str,
+    label: &'static str,
+    /// Temperature to show when the picker is first rendered
+    #[prop(default = None)]
+    initial: Option<TemperatureSpec>,
+    on_change: F,
+    /// Set while the input can't be read as a temperature
+    set_invalid: WriteSignal<bool>,
+) -> impl IntoView
+where
+    F: Fn(Option<TemperatureSpec>) + Copy + Send + Sync + 'static,
+{
+    let (initial_mode, initial_exact, initial_min, initial_max) = match initial {
+        None => (TemperatureMode::Unspecified, None, None, None),
+        Some(TemperatureSpec::Exact(t)) => (TemperatureMode::Exact, Some(t), None, None),
+        Some(TemperatureSpec::Range { min, max }) => {
+            (TemperatureMode::Range, None, Some(min), Some(max))
+        }
+    };
+    let to_string = |t: Option<u16>| t.map(|t| t.to_string()).unwrap_or_default();
+
+    let (mode, set_mode) = signal(initial_mode);
+    let (exact, set_exact) = signal(to_string(initial_exact));
+    let (min, set_min) = signal(to_string(initial_min));
+    let (max, set_max) = signal(to_string(initial_max));
+
+    let spec = Memo::new(move |_| -> Result<Option<TemperatureSpec>, String> {
+        match mode.get() {
+            TemperatureMode::Unspecified => Ok(None),
+            TemperatureMode::Exact => {
+                parse_temp(&exact.get()).map(|t| Some(TemperatureSpec::Exact(t)))
+            }
+            TemperatureMode::Range => {
+                let min = parse_temp(&min.get()).map_err(|e| format!("Min: {e}"))?;
+                let max = parse_temp(&max.get()).map_err(|e| format!("Max: {e}"))?;
+
+                if min > max {
+                    return Err("Min must be ≤ max".to_string());
+                }
+
+                Ok(Some(TemperatureSpec::Range { min, max }))
+            }
+        }
+    });
+
+    Effect::new(move |_| {
+        let spec = spec.get();
+        set_invalid.set(spec.is_err());
+
+        if let Ok(s) = spec {
+            on_change(s);
+        }
+    });
+
+    let error = move || spec.get().err();
+    let mode_id = format!("{id}-mode");
+    let exact_id = format!("{id}-exact");
+    let min_id = format!("{id}-min");
+    let max_id = format!("{id}-max");
+    let described_by = move || error().map(|_| format!("{id}-error"));
+
+    view! {
+        <div>
+            <label for=mode_id.clone()>{label}</label>
+            <select
+                class="input"
+                id=mode_id
+                prop:value=move || match mode.get() {
+                    TemperatureMode::Unspecified => "unspecified",
+                    TemperatureMode::Exact => "exact",
+                    TemperatureMode::Range => "range",
+                }
+                on:change=move |e| {
+                    set_mode.set(match event_target_value(&e).as_str() {
+                        "exact" => TemperatureMode::Exact,
+                        "range" => TemperatureMode::Range,
+                        _ => TemperatureMode::Unspecified,
+                    })
+                }
+            >
+                <option value="unspecified">"Unspecified"</option>
+                <option value="exact">"Exact"</option>
+                <option value="range">"Range"</option>
+            </select>
+
+            <Show when=move || mode.get() == TemperatureMode::Exact>
+                <label for=exact_id.clone()>"°C"</label>
+                <input
+                    class="input"
+                    id=exact_id.clone()
+                    type="number"
+                    min="0"
+                    max=MAX_TEMP_C
+                    placeholder="e.g. 210"
+                    aria-invalid=move || error().is_some().to_string()
+                    aria-describedby=described_by
+                    prop:value=move || exact.get()
+                    on:input=move |e| set_exact.set(event_target_value(&e))
+                />
+            </Show>
+
+            <Show when=move || mode.get() == TemperatureMode::Range>
+                <div class="input-box">
+                    <div class="min-box">
+                        <label for=min_id.clone()>"Min °C"</label>
+                        <input
+                            class="input"
+                            id=min_id.clone()
+                            type="number"
+                            min="0"
+                            max=MAX_TEMP_C
+                            placeholder="e.g. 190"
+                            aria-invalid=move || error().is_some().to_string()
+                            aria-describedby=described_by
+                            prop:value=move || min.get()
+                            on:input=move |e| set_min.set(event_target_value(&e))
+                        />
+                    </div>
+                    <div class="max-box">
+                        <label for=max_id.clone()>"Max °C"</label>
+                        <input
+                            class="input"
+                            id=max_id.clone()
+                            type="number"
+                            min="0"
+                            max=MAX_TEMP_C
+                            placeholder="e.g. 220"
+                            aria-invalid=move || error().is_some().to_string()
+                            aria-describedby=described_by
+                            prop:value=move || max.get()
+                            on:input=move |e| set_max.set(event_target_value(&e))
+                        />
+                    </div>
                 </div>
-                <Show when=move || result_message.get().is_some()>
-                    {move || match result_message.get().unwrap() {
-                        ResultMessage::Success(s) => view! { <p class="success">{s}</p> }.into_view(),
-                        ResultMessage::Error(s)   => view! { <p class="error">{s}</p> }.into_view(),
-                    }}
-                </Show>
-            </section>
+            </Show>
+
+            {move || {
+                error().map(|e| view! { <p class="error" id=format!("{id}-error") role="alert">{e}</p> })
+            }}
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a create attempt the way `save_product` does, returning the key it sent
+    fn attempt(
+        pending: &mut Option<PendingCreate>,
+        keys: &mut impl Iterator<Item = &'static str>,
+        body: &str,
+        response: Result<(), ErrorResponse>,
+    ) -> String {
+        let key = create_attempt_key(pending, body.to_string(), || {
+            keys.next().unwrap().to_string()
+        });
+
+        if !keeps_create_key(&response) {
+            *pending = None;
+        }
+
+        key
+    }
+
+    fn rejected() -> ErrorResponse {
+        ErrorResponse {
+            message: "Invalid product".to_string(),
+            status: 422,
+            fields: Default::default(),
+        }
+    }
+
+    #[test]
+    fn retried_create_reuses_idempotency_key() {
+        let mut pending = None;
+        let mut keys = ["k1", "k2"].into_iter();
+        let lost = || Err(ErrorResponse::network("Failed to fetch"));
+
+        assert_eq!(attempt(&mut pending, &mut keys, "{}", lost()), "k1");
+        assert_eq!(attempt(&mut pending, &mut keys, "{}", lost()), "k1");
+        assert_eq!(attempt(&mut pending, &mut keys, "{}", Ok(())), "k1");
+    }
+
+    #[test]
+    fn edited_create_gets_new_key() {
+        let mut pending = None;
+        let mut keys = ["k1", "k2", "k3"].into_iter();
+        let lost = || Err(ErrorResponse::network("Failed to fetch"));
+
+        assert_eq!(
+            attempt(&mut pending, &mut keys, r#"{"name":"PLA"}"#, lost()),
+            "k1"
+        );
+        assert_eq!(
+            attempt(&mut pending, &mut keys, r#"{"name":"PETG"}"#, lost()),
+            "k2"
+        );
+        // Going back to the first body doesn't bring its key back
+        assert_eq!(
+            attempt(&mut pending, &mut keys, r#"{"name":"PLA"}"#, Ok(())),
+            "k3"
+        );
+    }
+
+    #[test]
+    fn answered_create_gets_new_key_next_time() {
+        let mut pending = None;
+        let mut keys = ["k1", "k2", "k3"].into_iter();
+
+        assert_eq!(attempt(&mut pending, &mut keys, "{}", Ok(())), "k1");
+        assert_eq!(
+            attempt(&mut pending, &mut keys, "{}", Err(rejected())),
+            "k2"
+        );
+        assert_eq!(attempt(&mut pending, &mut keys, "{}", Ok(())), "k3");
+    }
+
+    #[test]
+    fn uuid_shape_accepts_uuid() {
+        assert!(is_uuid_shaped("0f8fad5b-d9cb-469f-a165-70867728950e"));
+        assert!(is_uuid_shaped("0F8FAD5B-D9CB-469F-A165-70867728950E"));
+    }
+
+    #[test]
+    fn uuid_shape_rejects_wrong_group_lengths() {
+        assert!(!is_uuid_shaped("0f8fad5-bd9cb-469f-a165-70867728950e"));
+        assert!(!is_uuid_shaped("0f8fad5b-d9cb-469f-a165-70867728950"));
+        assert!(!is_uuid_shaped("0f8fad5b-d9cb-469f-a165-70867728950ee"));
+    }
+
+    #[test]
+    fn uuid_shape_rejects_non_hex() {
+        assert!(!is_uuid_shaped("0f8fad5b-d9cb-469f-a165-70867728950g"));
+        assert!(!is_uuid_shaped("0f8fad5b-d9cb-469f-a165-7086772895 e"));
+    }
+
+    #[test]
+    fn uuid_shape_rejects_missing_or_extra_groups() {
+        assert!(!is_uuid_shaped("0f8fad5b-d9cb-469f-a16570867728950e"));
+        assert!(!is_uuid_shaped("0f8fad5bd9cb469fa16570867728950e"));
+        assert!(!is_uuid_shaped("0f8fad5b-d9cb-469f-a165-70867728950e-0000"));
+    }
+
+    #[test]
+    fn uuid_shape_rejects_empty() {
+        assert!(!is_uuid_shaped(""));
+    }
+
+    #[test]
+    fn plausible_diameter_has_no_warning() {
+        for input in ["1.75", "2,85", "1", "4", " 3.00 "] {
+            assert_eq!(custom_diameter_warning(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn unreadable_diameter_has_no_warning() {
+        assert_eq!(custom_diameter_warning(""), None);
+        assert_eq!(custom_diameter_warning("abc"), None);
+    }
+
+    #[test]
+    fn hundredths_suggest_mm() {
+        let (warning, suggestion) = custom_diameter_warning("175").unwrap();
+
+        assert_eq!(warning, "175 mm is outside 1–4 mm.");
+        assert_eq!(suggestion.as_deref(), Some("1.75"));
+    }
+
+    #[test]
+    fn tenths_suggest_mm() {
+        assert_eq!(
+            custom_diameter_warning("28.5")
+                .and_then(|(_, s)| s)
+                .as_deref(),
+            Some("2.85")
+        );
+    }
+
+    #[test]
+    fn diameters_validate_rejects_are_warned() {
+        for input in ["0.75", "5", "4.01", "0.99"] {
+            let (_, suggestion) = custom_diameter_warning(input).expect(input);
+
+            assert_eq!(suggestion, None, "{input}");
+        }
+    }
+
+    #[test]
+    fn preset_diameter_by_mm() {
+        assert_eq!(
+            diameter_from_select("1.75", FilamentDiameter::Unspecified),
+            FilamentDiameter::D175
+        );
+        assert_eq!(
+            diameter_from_select("2.85", FilamentDiameter::D175),
+            FilamentDiameter::D285
+        );
+    }
+
+    #[test]
+    fn unspecified_diameter() {
+        assert_eq!(
+            diameter_from_select("Unspecified", FilamentDiameter::D175),
+            FilamentDiameter::Unspecified
+        );
+    }
+
+    #[test]
+    fn other_keeps_custom_diameter() {
+        assert_eq!(
+            diameter_from_select("Other", FilamentDiameter::Other(300)),
+            FilamentDiameter::Other(300)
+        );
+    }
+
+    #[test]
+    fn other_starts_empty_from_preset() {
+        for current in [FilamentDiameter::D175, FilamentDiameter::Unspecified] {
+            assert_eq!(
+                diameter_from_select("Other", current),
+                FilamentDiameter::Other(0)
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_value_keeps_current() {
+        assert_eq!(
+            diameter_from_select("3.00", FilamentDiameter::D285),
+            FilamentDiameter::D285
+        );
+        assert_eq!(
+            diameter_from_select("", FilamentDiameter::Other(300)),
+            FilamentDiameter::Other(300)
+        );
+    }
+}
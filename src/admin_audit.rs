@@ -0,0 +1,350 @@
+use std::collections::HashMap;
+
+use gloo_storage::{LocalStorage, Storage};
+use leptos::{prelude::*, reactive::spawn_local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    product::{
+        Cents, Currency, FilamentColor, FilamentDiameter, FilamentMaterial, Grams, Product,
+        Retailer,
+    },
+    product_search::{Pagination, ProductSearchRequest, SortBy, search_products},
+};
+
+const AUDIT_PER_PAGE: u32 = 25;
+/// Products as they were the last time the audit showed them, used to summarize what changed
+/// since. Holds only [`AuditFields`], oldest first.
+const AUDIT_SNAPSHOT_KEY: &str = "audit_snapshot_v2";
+/// Held whole products and was never pruned
+const LEGACY_AUDIT_SNAPSHOT_V1_KEY: &str = "audit_snapshot_v1";
+/// Most products the snapshot remembers; the ones seen longest ago are dropped first
+const MAX_AUDIT_SNAPSHOT: usize = 20 * AUDIT_PER_PAGE as usize;
+
+/// The parts of a product [`summarize_changes`] compares
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct AuditFields {
+    name: String,
+    price: Cents,
+    list_price: Option<Cents>,
+    currency: Currency,
+    url: String,
+    material: FilamentMaterial,
+    diameter: FilamentDiameter,
+    weight: Grams,
+    retailer: Retailer,
+    color: FilamentColor,
+}
+
+impl From<&Product> for AuditFields {
+    fn from(p: &Product) -> Self {
+        AuditFields {
+            name: p.name.clone(),
+            price: p.price,
+            list_price: p.list_price,
+            currency: p.currency,
+            url: p.url.clone(),
+            material: p.material.clone(),
+            diameter: p.diameter,
+            weight: p.weight,
+            retailer: p.retailer.clone(),
+            color: p.color.clone(),
+        }
+    }
+}
+
+/// Adds `seen` to the end of `snapshot`, replacing older entries for the same UUIDs, then drops
+/// the entries seen longest ago until at most `max` remain
+fn remember<T>(snapshot: &mut Vec<(String, T)>, seen: Vec<(String, T)>, max: usize) {
+    snapshot.retain(|(uuid, _)| !seen.iter().any(|(s, _)| s == uuid));
+    snapshot.extend(seen);
+
+    let excess = snapshot.len().saturating_sub(max);
+    snapshot.drain(..excess);
+}
+
+/// Human-readable list of fields that differ between two versions of a product
+fn summarize_changes(old: &AuditFields, new: &AuditFields) -> Vec<String> {
+    let mut changes = vec![];
+
+    fn changed<T: PartialEq + ToString>(changes: &mut Vec<String>, label: &str, old: &T, new: &T) {
+        if old != new {
+            changes.push(format!(
+                "{label}: {} → {}",
+                old.to_string(),
+                new.to_string()
+            ));
+        }
+    }
+
+    changed(&mut changes, "Name", &old.name, &new.name);
+    changed(
+        &mut changes,
+        "Price",
+        &old.price.format_in(old.currency),
+        &new.price.format_in(new.currency),
+    );
+    changed(&mut changes, "Material", &old.material, &new.material);
+    changed(&mut changes, "Color", &old.color, &new.color);
+    changed(&mut changes, "Diameter", &old.diameter, &new.diameter);
+    changed(&mut changes, "Weight", &old.weight, &new.weight);
+    changed(&mut changes, "Retailer", &old.retailer, &new.retailer);
+
+    if old.list_price != new.list_price {
+        changes.push("List price changed".to_string());
+    }
+
+    if old.url != new.url {
+        changes.push("URL changed".to_string());
+    }
+
+    changes
+}
+
+#[component]
+pub fn AdminAudit() -> impl IntoView {
+    let (products, set_products) = signal::<Vec<Product>>(vec![]);
+    let (changes, set_changes) = signal::<HashMap<String, Vec<String>>>(HashMap::new());
+    let (loading, set_loading) = signal(false);
+    let (page, set_page) = signal(1u32);
+    let (total_pages, set_total_pages) = signal(1u32);
+
+    let (editor_filter, set_editor_filter) = signal(String::new());
+    let (from_date, set_from_date) = signal(String::new());
+    let (to_date, set_to_date) = signal(String::new());
+    let (newest_first, set_newest_first) = signal(true);
+
+    Effect::new(move |_| {
+        let request = ProductSearchRequest {
+            name: None,
+            min_price: None,
+            max_price: None,
+            currency: None,
+            material: None,
+            materials: None,
+            diameter: None,
+            weight: None,
+            color: None,
+            page: page.get(),
+            per_page: AUDIT_PER_PAGE,
+            sort_by: Some(SortBy::UpdatedAt),
+            sort: vec![],
+            on_sale: None,
+            tags: None,
+            tag_match: None,
+            region: None,
+            region_mode: None,
+            hide_unknown_region: None,
+            exclude_unspecified_material: None,
+            exclude_materials: None,
+            exclude_unspecified_color: None,
+            aggregates: None,
+        };
+
+        spawn_local(async move {
+            set_loading.set(true);
+            let response = search_products(&request).await;
+
+            LocalStorage::delete(LEGACY_AUDIT_SNAPSHOT_V1_KEY);
+            let mut snapshot: Vec<(String, AuditFields)> =
+                LocalStorage::get(AUDIT_SNAPSHOT_KEY).unwrap_or_default();
+            let seen: Vec<(String, AuditFields)> = response
+                .items
+                .iter()
+                .map(|p| (p.uuid.clone(), AuditFields::from(p)))
+                .collect();
+
+            set_changes.set(
+                seen.iter()
+                    .filter_map(|(uuid, new)| {
+                        snapshot
+                            .iter()
+                            .find(|(u, _)| u == uuid)
+                            .map(|(_, old)| (uuid.clone(), summarize_changes(old, new)))
+                    })
+                    .collect(),
+            );
+
+            remember(&mut snapshot, seen, MAX_AUDIT_SNAPSHOT);
+            if let Err(e) = LocalStorage::set(AUDIT_SNAPSHOT_KEY, &snapshot) {
+                crate::console_warn(format!("Failed to save the audit snapshot: {e}"));
+            }
+
+            set_products.set(response.items);
+            set_total_pages.set(response.total_pages as u32);
+            set_loading.set(false);
+        });
+    });
+
+    let editors = move || {
+        let mut editors: Vec<String> = products
+            .get()
+            .into_iter()
+            .filter_map(|p| p.updated_by)
+            .collect();
+        editors.sort();
+        editors.dedup();
+        editors
+    };
+
+    let visible = move || {
+        let editor = editor_filter.get();
+        let from = from_date.get();
+        let to = to_date.get();
+
+        let mut visible: Vec<Product> = products
+            .get()
+            .into_iter()
+            .filter(|p| editor.is_empty() || p.updated_by.as_deref() == Some(editor.as_str()))
+            .filter(|p| {
+                // RFC 3339 timestamps compare correctly as strings against a YYYY-MM-DD prefix
+                let date = p.updated_at.as_deref().map(|d| d.get(..10).unwrap_or(d));
+                (from.is_empty() || date.is_some_and(|d| d >= from.as_str()))
+                    && (to.is_empty() || date.is_some_and(|d| d <= to.as_str()))
+            })
+            .collect();
+
+        visible.sort_by(|a, b| a.updated_at.cmp(&b.updated_at));
+
+        if newest_first.get() {
+            visible.reverse();
+        }
+
+        visible
+    };
+
+    view! {
+        <div class="container full-width">
+            <h2>"Recently Changed"</h2>
+            <section style="display: grid; gap: 12px;">
+                <div class="options-row">
+                    <div>
+                        <label>"Editor"</label>
+                        <select
+                            class="input"
+                            prop:value=move || editor_filter.get()
+                            on:change=move |e| set_editor_filter.set(event_target_value(&e))
+                        >
+                            <option value="">"Any"</option>
+                            {move || {
+                                editors()
+                                    .into_iter()
+                                    .map(|e| view! { <option value=e.clone()>{e.clone()}</option> })
+                                    .collect_view()
+                            }}
+                        </select>
+                    </div>
+                    <div>
+                        <label>"From"</label>
+                        <input
+                            class="input"
+                            type="date"
+                            prop:value=move || from_date.get()
+                            on:input=move |e| set_from_date.set(event_target_value(&e))
+                        />
+                    </div>
+                    <div>
+                        <label>"To"</label>
+                        <input
+                            class="input"
+                            type="date"
+                            prop:value=move || to_date.get()
+                            on:input=move |e| set_to_date.set(event_target_value(&e))
+                        />
+                    </div>
+                </div>
+
+                {move || {
+                    if loading.get() {
+                        view! { <div class="loading">"Loading..."</div> }.into_any()
+                    } else if visible().is_empty() {
+                        view! { <div class="empty">"No recent changes match."</div> }.into_any()
+                    } else {
+                        view! {
+                            <table style="width: 100%;">
+                                <thead>
+                                    <tr>
+                                        <th>
+                                            <button
+                                                style="width: auto;"
+                                                on:click=move |_| set_newest_first.update(|n| *n = !*n)
+                                            >
+                                                {move || if newest_first.get() { "Updated ↓" } else { "Updated ↑" }}
+                                            </button>
+                                        </th>
+                                        <th>"Editor"</th>
+                                        <th>"Name"</th>
+                                        <th>"Changes"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <For
+                                        each=visible
+                                        key=|p| p.uuid.clone()
+                                        children=move |p: Product| {
+                                            let summary = match changes.with(|c| c.get(&p.uuid).cloned()) {
+                                                None => "First seen".to_string(),
+                                                Some(c) if c.is_empty() => "No changes since last review".to_string(),
+                                                Some(c) => c.join(", "),
+                                            };
+                                            let updated_at = p
+                                                .updated_at
+                                                .as_deref()
+                                                .map(|d| d.get(..16).unwrap_or(d).replace('T', " "))
+                                                .unwrap_or_else(|| "—".to_string());
+
+                                            view! {
+                                                <tr>
+                                                    <td>{updated_at}</td>
+                                                    <td>{p.updated_by.clone().unwrap_or_else(|| "—".to_string())}</td>
+                                                    <td style="text-align: left;">
+                                                        <a href=format!("/admin?product={}", p.uuid)>{p.name.clone()}</a>
+                                                    </td>
+                                                    <td style="text-align: left;">{summary}</td>
+                                                </tr>
+                                            }
+                                        }
+                                    />
+                                </tbody>
+                            </table>
+                        }
+                        .into_any()
+                    }
+                }}
+
+                <Pagination page=page total_pages=total_pages set_page=set_page />
+            </section>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries(uuids: &[&str]) -> Vec<(String, ())> {
+        uuids.iter().map(|u| (u.to_string(), ())).collect()
+    }
+
+    fn uuids(snapshot: &[(String, ())]) -> Vec<&str> {
+        snapshot.iter().map(|(u, _)| u.as_str()).collect()
+    }
+
+    #[test]
+    fn remember_keeps_newest_within_cap() {
+        let mut snapshot = entries(&["a", "b", "c"]);
+
+        remember(&mut snapshot, entries(&["d", "e"]), 4);
+
+        assert_eq!(uuids(&snapshot), ["b", "c", "d", "e"]);
+    }
+
+    #[test]
+    fn remember_moves_seen_again_to_end() {
+        let mut snapshot = entries(&["a", "b", "c"]);
+
+        remember(&mut snapshot, entries(&["a"]), 3);
+
+        assert_eq!(uuids(&snapshot), ["b", "c", "a"]);
+    }
+}
@@ -0,0 +1,469 @@
+use std::collections::HashMap;
+
+use gloo_net::http::Method;
+use leptos::{prelude::*, reactive::spawn_local};
+
+use crate::{
+    confirm::TypedConfirm,
+    product::{Cents, Product},
+    product_search::{
+        ColorFilter, DiameterFilter, FilterBar, MaterialFilter, Pagination, ProductSearchRequest,
+        WeightFilter, search_products,
+    },
+    request::{Auth, request_json},
+};
+
+const ADMIN_PER_PAGE: u32 = 25;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BulkOperation {
+    SetPrice,
+    AdjustPercent,
+    MarkOutOfStock,
+}
+
+impl BulkOperation {
+    /// Whether the operation needs a number typed into the value field
+    fn takes_value(&self) -> bool {
+        !matches!(self, BulkOperation::MarkOutOfStock)
+    }
+
+    fn apply(&self, product: &mut Product, value: f32) {
+        match self {
+            BulkOperation::SetPrice => product.price = Cents::from_dollars(value),
+            BulkOperation::AdjustPercent => {
+                product.price = product.price.adjusted_by_percent(value)
+            }
+            BulkOperation::MarkOutOfStock => product.in_stock = Some(false),
+        }
+    }
+}
+
+#[component]
+pub fn AdminProductList() -> impl IntoView {
+    let (query, set_query) = signal(String::new());
+    let (mat_filter, set_mat_filter) = signal(MaterialFilter::Any);
+    let (col_filter, set_col_filter) = signal(ColorFilter::Any);
+    let (diam_filter, set_diam_filter) = signal(DiameterFilter::Any);
+    let (weight_filter, set_weight_filter) = signal(WeightFilter::Any);
+    let (products, set_products) = signal::<Vec<Product>>(vec![]);
+    let (loading, set_loading) = signal(false);
+    let (page, set_page) = signal(1u32);
+    let (total_pages, set_total_pages) = signal(1u32);
+
+    // Selected products keyed by UUID, kept across pages so a bulk operation can span them
+    let selected = RwSignal::new(HashMap::<String, Product>::new());
+
+    let (bulk_op, set_bulk_op) = signal(BulkOperation::SetPrice);
+    let (bulk_value, set_bulk_value) = signal(String::new());
+    let (bulk_error, set_bulk_error) = signal::<Option<String>>(None);
+    let (progress, set_progress) = signal::<Option<(usize, usize)>>(None);
+    let (failures, set_failures) = signal::<Vec<String>>(vec![]);
+    // Changes waiting on the typed confirmation
+    let pending_bulk = RwSignal::new(Vec::<Product>::new());
+    let confirm_bulk = RwSignal::new(false);
+
+    let load = move || {
+        let name = query.get_untracked().trim().to_string();
+
+        let request = ProductSearchRequest {
+            name: if name.is_empty() { None } else { Some(name) },
+            min_price: None,
+            max_price: None,
+            currency: None,
+            material: mat_filter.get_untracked().to_material(),
+            materials: mat_filter.get_untracked().to_materials(),
+            diameter: diam_filter.get_untracked().to_diameter(),
+            weight: weight_filter.get_untracked().to_weight(),
+            color: col_filter.get_untracked().to_color(),
+            page: page.get_untracked(),
+            per_page: ADMIN_PER_PAGE,
+            sort_by: None,
+            sort: vec![],
+            on_sale: None,
+            tags: None,
+            tag_match: None,
+            region: None,
+            region_mode: None,
+            hide_unknown_region: None,
+            exclude_unspecified_material: None,
+            exclude_materials: None,
+            exclude_unspecified_color: None,
+            aggregates: None,
+        };
+
+        spawn_local(async move {
+            set_loading.set(true);
+            let response = search_products(&request).await;
+            set_products.set(response.items);
+            set_total_pages.set(response.total_pages as u32);
+            set_loading.set(false);
+        });
+    };
+
+    Effect::new(move |_| {
+        let _ = page.get();
+        load();
+    });
+
+    let on_search = move || {
+        if page.get_untracked() == 1 {
+            load();
+        } else {
+            set_page.set(1);
+        }
+    };
+
+    let running = move || progress.get().is_some_and(|(done, total)| done < total);
+
+    // Saves each product in turn, deselecting it on success and collecting failures
+    let run_bulk = move |targets: Vec<Product>| {
+        let total = targets.len();
+
+        set_failures.set(vec![]);
+        set_progress.set(Some((0, total)));
+
+        spawn_local(async move {
+            for (i, product) in targets.into_iter().enumerate() {
+                let path = format!("products/{}", product.uuid);
+
+                let res = request_json::<Product, Product>(
+                    &path,
+                    Auth::Authorized,
+                    Method::POST,
+                    Some(&product),
+                )
+                .await;
+
+                match res {
+                    Ok(_) => {
+                        selected.update(|s| {
+                            s.remove(&product.uuid);
+                        });
+                    }
+                    Err(e) => {
+                        set_failures.update(|f| {
+                            f.push(format!("{}: ({}) {}", product.name, e.status, e.message))
+                        });
+                    }
+                }
+
+                set_progress.set(Some((i + 1, total)));
+            }
+
+            load();
+        });
+    };
+
+    let on_apply = move |_| {
+        set_bulk_error.set(None);
+
+        let op = bulk_op.get_untracked();
+
+        let value = if op.takes_value() {
+            match bulk_value.get_untracked().trim().parse::<f32>() {
+                Ok(v) if v.is_finite() => v,
+                _ => {
+                    set_bulk_error.set(Some("Enter a number".to_string()));
+                    return;
+                }
+            }
+        } else {
+            0.0
+        };
+
+        if op == BulkOperation::SetPrice && value < 0.0 {
+            set_bulk_error.set(Some("Price cannot be negative".to_string()));
+            return;
+        }
+
+        let targets = selected
+            .get_untracked()
+            .into_values()
+            .map(|mut p| {
+                op.apply(&mut p, value);
+                p
+            })
+            .collect();
+
+        pending_bulk.set(targets);
+        confirm_bulk.set(true);
+    };
+
+    // Re-saving a product unchanged makes the backend recompute its derived `price_per_kg`
+    let on_recompute = move |_| {
+        set_bulk_error.set(None);
+        run_bulk(selected.get_untracked().into_values().collect());
+    };
+
+    let stale_count =
+        move || products.with(|p| p.iter().filter(|p| p.price_per_kg_is_stale()).count());
+
+    let all_selected = move || {
+        let products = products.get();
+        !products.is_empty() && selected.with(|s| products.iter().all(|p| s.contains_key(&p.uuid)))
+    };
+
+    view! {
+        <div class="container full-width">
+            <h2>"Products"</h2>
+            <section style="display: grid; gap: 12px;">
+                <div class="options-row">
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Search by name…"
+                        prop:value=move || query.get()
+                        on:input=move |e| set_query.set(event_target_value(&e))
+                        on:keydown=move |e| {
+                            if e.key() == "Enter" {
+                                on_search();
+                            }
+                        }
+                    />
+                    <div>
+                        <button on:click=move |_| on_search()>"Search"</button>
+                    </div>
+                </div>
+                <FilterBar
+                    mat_filter=mat_filter
+                    set_mat_filter=set_mat_filter
+                    col_filter=col_filter
+                    set_col_filter=set_col_filter
+                    diam_filter=diam_filter
+                    set_diam_filter=set_diam_filter
+                    weight_filter=weight_filter
+                    set_weight_filter=set_weight_filter
+                />
+
+                {move || {
+                    if loading.get() {
+                        view! { <div class="loading">"Loading..."</div> }.into_any()
+                    } else if products.get().is_empty() {
+                        view! { <div class="empty">"No products found."</div> }.into_any()
+                    } else {
+                        view! {
+                            <table style="width: 100%;">
+                                <thead>
+                                    <tr>
+                                        <th>
+                                            <input
+                                                type="checkbox"
+                                                style="width: auto;"
+                                                prop:checked=all_selected
+                                                on:change=move |e| {
+                                                    let checked = event_target_checked(&e);
+                                                    let page_products = products.get_untracked();
+
+                                                    selected.update(|s| {
+                                                        for p in page_products {
+                                                            if checked {
+                                                                s.insert(p.uuid.clone(), p);
+                                                            } else {
+                                                                s.remove(&p.uuid);
+                                                            }
+                                                        }
+                                                    });
+                                                }
+                                            />
+                                        </th>
+                                        <th>"Name"</th>
+                                        <th>"Price"</th>
+                                        <th>"Price / kg"</th>
+                                        <th>"Retailer"</th>
+                                    </tr>
+                                </thead>
+                                <tbody>
+                                    <For
+                                        each=move || products.get()
+                                        key=|p| p.uuid.clone()
+                                        children=move |p: Product| {
+                                            let uuid = p.uuid.clone();
+                                            let product = p.clone();
+
+                                            view! {
+                                                <tr>
+                                                    <td>
+                                                        <input
+                                                            type="checkbox"
+                                                            style="width: auto;"
+                                                            prop:checked=move || selected.with(|s| s.contains_key(&uuid))
+                                                            on:change=move |e| {
+                                                                let checked = event_target_checked(&e);
+                                                                let product = product.clone();
+
+                                                                selected.update(|s| {
+                                                                    if checked {
+                                                                        s.insert(product.uuid.clone(), product);
+                                                                    } else {
+                                                                        s.remove(&product.uuid);
+                                                                    }
+                                                                });
+                                                            }
+                                                        />
+                                                    </td>
+                                                    <td style="text-align: left;">
+                                                        <a href=format!("/admin?product={}", p.uuid)>{p.name.clone()}</a>
+                                                    </td>
+                                                    <td>{p.price.format_in(p.currency)}</td>
+                                                    <td>
+                                                        {p.price_per_kg.format_in(p.currency)}
+                                                        {p.price_per_kg_is_stale().then(|| {
+                                                            let expected = p.expected_price_per_kg().unwrap_or(Cents(0));
+                                                            view! {
+                                                                <span
+                                                                    class="price-note"
+                                                                    title=format!("Price and weight give {}", expected.format_in(p.currency))
+                                                                >
+                                                                    " ⚠ stale"
+                                                                </span>
+                                                            }
+                                                        })}
+                                                    </td>
+                                                    <td>{p.retailer.to_string()}</td>
+                                                </tr>
+                                            }
+                                        }
+                                    />
+                                </tbody>
+                            </table>
+                        }
+                        .into_any()
+                    }
+                }}
+
+                <Show when=move || { stale_count() > 0 }>
+                    <p class="price-note">
+                        {move || {
+                            format!(
+                                "{} product(s) on this page have a $/kg that doesn't match their price and weight. ",
+                                stale_count(),
+                            )
+                        }}
+                        <button
+                            class="link-button"
+                            on:click=move |_| {
+                                let stale = products
+                                    .get_untracked()
+                                    .into_iter()
+                                    .filter(|p| p.price_per_kg_is_stale());
+                                selected.update(|s| s.extend(stale.map(|p| (p.uuid.clone(), p))));
+                            }
+                        >
+                            "Select them"
+                        </button>
+                    </p>
+                </Show>
+
+                <Pagination page=page total_pages=total_pages set_page=set_page />
+
+                <h3>"Bulk Update"</h3>
+                <div class="options-row">
+                    <div>
+                        <label>"Operation"</label>
+                        <select
+                            class="input"
+                            prop:value=move || match bulk_op.get() {
+                                BulkOperation::SetPrice => "set",
+                                BulkOperation::AdjustPercent => "percent",
+                                BulkOperation::MarkOutOfStock => "out-of-stock",
+                            }
+                            on:change=move |e| {
+                                match event_target_value(&e).as_str() {
+                                    "percent" => set_bulk_op.set(BulkOperation::AdjustPercent),
+                                    "out-of-stock" => set_bulk_op.set(BulkOperation::MarkOutOfStock),
+                                    _ => set_bulk_op.set(BulkOperation::SetPrice),
+                                }
+                            }
+                        >
+                            <option value="set">"Set price"</option>
+                            <option value="percent">"Adjust by percentage"</option>
+                            <option value="out-of-stock">"Mark out of stock"</option>
+                        </select>
+                    </div>
+                    <div>
+                        <label>"Value"</label>
+                        <input
+                            class="input"
+                            type="text"
+                            placeholder=move || match bulk_op.get() {
+                                BulkOperation::SetPrice => "In each product's currency (e.g. 19.99)",
+                                BulkOperation::AdjustPercent => "Percent (e.g. -10)",
+                                BulkOperation::MarkOutOfStock => "Not needed",
+                            }
+                            disabled=move || !bulk_op.get().takes_value()
+                            prop:value=move || bulk_value.get()
+                            on:input=move |e| set_bulk_value.set(event_target_value(&e))
+                        />
+                    </div>
+                    <div>
+                        <button
+                            disabled=move || running() || selected.with(|s| s.is_empty())
+                            on:click=on_apply
+                        >
+                            {move || format!("Apply to {} selected", selected.with(|s| s.len()))}
+                        </button>
+                    </div>
+                    <div>
+                        <button
+                            disabled=move || running() || selected.with(|s| s.is_empty())
+                            on:click=on_recompute
+                        >
+                            "Recompute $/kg"
+                        </button>
+                    </div>
+                </div>
+                <TypedConfirm
+                    open=confirm_bulk
+                    title="Confirm bulk update"
+                    message=Signal::derive(move || {
+                        let count = pending_bulk.with(|p| p.len());
+                        match bulk_op.get() {
+                            BulkOperation::SetPrice => format!(
+                                "This sets the price of {count} products to {} in each product's currency.",
+                                bulk_value.get().trim()
+                            ),
+                            BulkOperation::AdjustPercent => format!(
+                                "This changes the price of {count} products by {}%.",
+                                bulk_value.get().trim()
+                            ),
+                            BulkOperation::MarkOutOfStock => {
+                                format!("This marks {count} products out of stock.")
+                            }
+                        }
+                    })
+                    expected=Signal::derive(move || pending_bulk.with(|p| p.len()).to_string())
+                    action_label="Update products"
+                    on_confirm=move || run_bulk(pending_bulk.get_untracked())
+                />
+                <Show when=move || bulk_error.get().is_some()>
+                    <p class="error">{move || bulk_error.get().unwrap_or_default()}</p>
+                </Show>
+                <Show when=move || progress.get().is_some()>
+                    {move || {
+                        let (done, total) = progress.get().unwrap_or_default();
+                        let failed = failures.with(|f| f.len());
+
+                        if done < total {
+                            view! { <p>{format!("Updating… {done} of {total}")}</p> }.into_any()
+                        } else if failed == 0 {
+                            view! { <p class="success">{format!("Updated {total} products")}</p> }.into_any()
+                        } else {
+                            view! {
+                                <p class="error">
+                                    {format!("Updated {} of {total} products, {failed} failed", total - failed)}
+                                </p>
+                            }
+                            .into_any()
+                        }
+                    }}
+                    <For
+                        each=move || failures.get()
+                        key=|f| f.clone()
+                        children=move |f| view! { <p class="error">{f}</p> }
+                    />
+                </Show>
+            </section>
+        </div>
+    }
+}
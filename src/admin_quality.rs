@@ -0,0 +1,285 @@
+use leptos::{prelude::*, reactive::spawn_local};
+
+use crate::{
+    product::{FilamentColor, FilamentMaterial, Product, ProductField},
+    product_search::{ProductSearchRequest, search_products},
+};
+
+const QUALITY_PER_PAGE: u32 = 50;
+/// Upper bound on pages fetched by one scan, so a huge catalog can't hang the page
+const QUALITY_MAX_PAGES: u32 = 40;
+
+/// A reason a product needs an admin's attention
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum QualityIssue {
+    Invalid(ProductField),
+    MissingMaterial,
+    MissingColor,
+    MissingTemperatures,
+    UnknownPricePerKg,
+    StalePricePerKg,
+}
+
+impl QualityIssue {
+    fn label(&self) -> &'static str {
+        match self {
+            QualityIssue::Invalid(ProductField::Name) => "Invalid name",
+            QualityIssue::Invalid(ProductField::Price) => "Invalid price",
+            QualityIssue::Invalid(ProductField::Weight) => "Invalid weight",
+            QualityIssue::Invalid(ProductField::Diameter) => "Invalid diameter",
+            QualityIssue::Invalid(ProductField::Url) => "Invalid URL",
+            QualityIssue::Invalid(ProductField::Material) => "Invalid material",
+            QualityIssue::Invalid(ProductField::Color) => "Invalid color",
+            QualityIssue::Invalid(ProductField::Description) => "Invalid description",
+            QualityIssue::MissingMaterial => "No material",
+            QualityIssue::MissingColor => "No color",
+            QualityIssue::MissingTemperatures => "No temperatures",
+            QualityIssue::UnknownPricePerKg => "$/kg unknown",
+            QualityIssue::StalePricePerKg => "$/kg stale",
+        }
+    }
+}
+
+fn quality_issues(product: &Product) -> Vec<QualityIssue> {
+    let mut issues: Vec<QualityIssue> = product
+        .validate()
+        .err()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|e| QualityIssue::Invalid(e.field))
+        .collect();
+
+    if product.material == FilamentMaterial::Unspecified {
+        issues.push(QualityIssue::MissingMaterial);
+    }
+
+    if product.color == FilamentColor::Unspecified {
+        issues.push(QualityIssue::MissingColor);
+    }
+
+    if product.nozzle_temp.is_none() || product.bed_temp.is_none() {
+        issues.push(QualityIssue::MissingTemperatures);
+    }
+
+    if product.displayable_price_per_kg().is_none() {
+        issues.push(QualityIssue::UnknownPricePerKg);
+    } else if product.price_per_kg_is_stale() {
+        issues.push(QualityIssue::StalePricePerKg);
+    }
+
+    issues.sort();
+    issues.dedup();
+    issues
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum QualitySort {
+    MostIssues,
+    ByIssue,
+    Name,
+}
+
+/// Scans the catalog for products with missing or invalid data, grouped by what's wrong
+#[component]
+pub fn AdminDataQuality() -> impl IntoView {
+    let (flagged, set_flagged) = signal::<Vec<(Product, Vec<QualityIssue>)>>(vec![]);
+    let (scanning, set_scanning) = signal(false);
+    let (scanned, set_scanned) = signal::<Option<(usize, u64)>>(None);
+    let (bucket, set_bucket) = signal::<Option<QualityIssue>>(None);
+    let (sort, set_sort) = signal(QualitySort::MostIssues);
+
+    let on_scan = move |_| {
+        set_scanning.set(true);
+        set_flagged.set(vec![]);
+        set_bucket.set(None);
+
+        spawn_local(async move {
+            let mut page = 1;
+            let mut seen = 0;
+
+            loop {
+                let request = ProductSearchRequest {
+                    name: None,
+                    min_price: None,
+                    max_price: None,
+                    currency: None,
+                    material: None,
+                    materials: None,
+                    diameter: None,
+                    weight: None,
+                    color: None,
+                    page,
+                    per_page: QUALITY_PER_PAGE,
+                    sort_by: None,
+                    sort: vec![],
+                    on_sale: None,
+                    tags: None,
+                    tag_match: None,
+                    region: None,
+                    region_mode: None,
+                    hide_unknown_region: None,
+                    exclude_unspecified_material: None,
+                    exclude_materials: None,
+                    exclude_unspecified_color: None,
+                    aggregates: None,
+                };
+
+                let response = search_products(&request).await;
+                seen += response.items.len();
+
+                let found: Vec<_> = response
+                    .items
+                    .into_iter()
+                    .map(|p| {
+                        let issues = quality_issues(&p);
+                        (p, issues)
+                    })
+                    .filter(|(_, issues)| !issues.is_empty())
+                    .collect();
+
+                set_flagged.update(|f| f.extend(found));
+                set_scanned.set(Some((seen, response.total)));
+
+                if page as u64 >= response.total_pages || page >= QUALITY_MAX_PAGES {
+                    break;
+                }
+                page += 1;
+            }
+
+            set_scanning.set(false);
+        });
+    };
+
+    let buckets = move || {
+        let mut counts: Vec<(QualityIssue, usize)> = vec![];
+
+        flagged.with(|f| {
+            for issue in f.iter().flat_map(|(_, issues)| issues) {
+                match counts.iter_mut().find(|(i, _)| i == issue) {
+                    Some((_, n)) => *n += 1,
+                    None => counts.push((*issue, 1)),
+                }
+            }
+        });
+
+        counts.sort();
+        counts
+    };
+
+    let visible = move || {
+        let bucket = bucket.get();
+        let mut visible: Vec<(Product, Vec<QualityIssue>)> = flagged
+            .get()
+            .into_iter()
+            .filter(|(_, issues)| bucket.is_none_or(|b| issues.contains(&b)))
+            .collect();
+
+        match sort.get() {
+            QualitySort::MostIssues => {
+                visible.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.name.cmp(&b.0.name)))
+            }
+            QualitySort::ByIssue => {
+                visible.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.name.cmp(&b.0.name)))
+            }
+            QualitySort::Name => visible.sort_by(|a, b| a.0.name.cmp(&b.0.name)),
+        }
+
+        visible
+    };
+
+    view! {
+        <div class="container full-width">
+            <h2>"Data Quality"</h2>
+            <section style="display: grid; gap: 12px;">
+                <div class="options-row">
+                    <div>
+                        <button disabled=move || scanning.get() on:click=on_scan>
+                            {move || if scanning.get() { "Scanning…" } else { "Scan catalog" }}
+                        </button>
+                    </div>
+                    <div>
+                        <label>"Sort"</label>
+                        <select
+                            class="input"
+                            on:change=move |e| {
+                                set_sort.set(match event_target_value(&e).as_str() {
+                                    "issue" => QualitySort::ByIssue,
+                                    "name" => QualitySort::Name,
+                                    _ => QualitySort::MostIssues,
+                                })
+                            }
+                        >
+                            <option value="most">"Most issues first"</option>
+                            <option value="issue">"Grouped by missing field"</option>
+                            <option value="name">"Name"</option>
+                        </select>
+                    </div>
+                </div>
+
+                {move || {
+                    scanned
+                        .get()
+                        .map(|(seen, total)| {
+                            let flagged = flagged.with(|f| f.len());
+                            view! { <p class="hint">{format!("Scanned {seen} of {total} products; {flagged} need attention")}</p> }
+                        })
+                }}
+
+                <div class="legend-tier">
+                    <button
+                        class="legend-material"
+                        class:active=move || bucket.get().is_none()
+                        on:click=move |_| set_bucket.set(None)
+                    >
+                        "All"
+                    </button>
+                    {move || {
+                        buckets()
+                            .into_iter()
+                            .map(|(issue, n)| {
+                                view! {
+                                    <button
+                                        class="legend-material"
+                                        class:active=move || bucket.get() == Some(issue)
+                                        on:click=move |_| set_bucket.set(Some(issue))
+                                    >
+                                        {format!("{} ({n})", issue.label())}
+                                    </button>
+                                }
+                            })
+                            .collect_view()
+                    }}
+                </div>
+
+                <Show when=move || !flagged.with(|f| f.is_empty())>
+                    <table style="width: 100%;">
+                        <thead>
+                            <tr>
+                                <th>"Name"</th>
+                                <th>"Issues"</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <For
+                                each=visible
+                                key=|(p, _)| p.uuid.clone()
+                                children=move |(p, issues): (Product, Vec<QualityIssue>)| {
+                                    let labels = issues.iter().map(|i| i.label()).collect::<Vec<_>>().join(", ");
+
+                                    view! {
+                                        <tr>
+                                            <td style="text-align: left;">
+                                                <a href=format!("/admin?product={}", p.uuid)>{p.name.clone()}</a>
+                                            </td>
+                                            <td style="text-align: left;">{labels}</td>
+                                        </tr>
+                                    }
+                                }
+                            />
+                        </tbody>
+                    </table>
+                </Show>
+            </section>
+        </div>
+    }
+}
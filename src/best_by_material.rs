@@ -0,0 +1,141 @@
+use gloo_storage::{SessionStorage, Storage};
+use leptos::{prelude::*, reactive::spawn_local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    product::{FilamentMaterial, Product},
+    product_search::{FilterState, MaterialFilter, SortBy, SortKey, try_search_products},
+};
+
+/// The materials beginners most often shop for, in display order
+const MATERIALS: &[FilamentMaterial] = &[
+    FilamentMaterial::PLA,
+    FilamentMaterial::PETG,
+    FilamentMaterial::ABS,
+    FilamentMaterial::TPU,
+];
+
+const CACHE_KEY: &str = "best_by_material_v1";
+/// How long the cheapest listings are reused before asking the server again
+const CACHE_MS: f64 = 5.0 * 60.0 * 1000.0;
+/// Listings fetched per material; a few, so one with an unknown weight at the top doesn't hide
+/// the real cheapest
+const CANDIDATES: u32 = 5;
+
+#[derive(Clone, Serialize, Deserialize)]
+enum Slot {
+    Loading,
+    Found(Box<Product>),
+    NoListings,
+    Failed,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Cached {
+    fetched_at_ms: f64,
+    slots: Vec<Slot>,
+}
+
+fn filters_for(material: &FilamentMaterial) -> FilterState {
+    FilterState {
+        material: MaterialFilter::Materials(vec![material.clone()]),
+        sort: vec![SortKey::asc(SortBy::PricePerKg)],
+        ..Default::default()
+    }
+}
+
+/// A fresh cache from earlier in this browser session, if there is one
+fn load_cached() -> Option<Vec<Slot>> {
+    let cached = SessionStorage::get::<Cached>(CACHE_KEY).ok()?;
+    let age = web_sys::js_sys::Date::now() - cached.fetched_at_ms;
+
+    (age < CACHE_MS && cached.slots.len() == MATERIALS.len()).then_some(cached.slots)
+}
+
+async fn fetch_cheapest(material: &FilamentMaterial) -> Slot {
+    let request = filters_for(material).to_request(1, CANDIDATES);
+
+    match try_search_products(&request).await {
+        Ok(response) => response
+            .items
+            .into_iter()
+            .find(|p| p.displayable_price_per_kg().is_some_and(|ppk| ppk.0 > 0))
+            .map_or(Slot::NoListings, |p| Slot::Found(Box::new(p))),
+        Err(_) => Slot::Failed,
+    }
+}
+
+/// The cheapest listing per kilogram for each common material, linking into a filtered search
+#[component]
+pub fn BestByMaterial() -> impl IntoView {
+    let (slots, set_slots) = signal(vec![Slot::Loading; MATERIALS.len()]);
+
+    if let Some(cached) = load_cached() {
+        set_slots.set(cached);
+    } else {
+        spawn_local(async move {
+            for (i, material) in MATERIALS.iter().enumerate() {
+                let slot = fetch_cheapest(material).await;
+                set_slots.update(|s| s[i] = slot);
+            }
+
+            // Failures aren't cached, so the next visit tries them again
+            slots.with_untracked(|slots| {
+                if !slots.iter().any(|s| matches!(s, Slot::Failed)) {
+                    let _ = SessionStorage::set(
+                        CACHE_KEY,
+                        Cached {
+                            fetched_at_ms: web_sys::js_sys::Date::now(),
+                            slots: slots.clone(),
+                        },
+                    );
+                }
+            });
+        });
+    }
+
+    let row = move |i: usize, material: &FilamentMaterial| {
+        // A full load, since the search reads its filters from the URL only when it starts
+        let search_href = format!("/?{}", filters_for(material).to_query());
+
+        view! {
+            <li>
+                <strong>{material.to_string()}</strong>
+                {move || match slots.with(|s| s[i].clone()) {
+                    Slot::Loading => view! { <span class="hint">"Loading…"</span> }.into_any(),
+                    Slot::Found(p) => {
+                        let price_per_kg = p
+                            .displayable_price_per_kg()
+                            .map(|ppk| format!("{}/kg", ppk.format_in(p.currency)))
+                            .unwrap_or_default();
+
+                        view! {
+                            <span>
+                                <a href=p.canonical_path()>{p.name.clone()}</a>
+                                " "
+                                <span class="best-price">{price_per_kg}</span>
+                            </span>
+                        }
+                            .into_any()
+                    }
+                    Slot::NoListings => view! { <span class="hint">"No listings"</span> }.into_any(),
+                    Slot::Failed => view! { <span class="hint">"Unavailable right now"</span> }.into_any(),
+                }}
+                <a href=search_href rel="external">{format!("All {material}")}</a>
+            </li>
+        }
+    };
+
+    view! {
+        <section class="best-by-material">
+            <h2>"Best price per kg right now"</h2>
+            <ul>
+                {MATERIALS
+                    .iter()
+                    .enumerate()
+                    .map(|(i, m)| row(i, m))
+                    .collect_view()}
+            </ul>
+        </section>
+    }
+}
@@ -0,0 +1,111 @@
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{
+    HtmlDocument, HtmlTextAreaElement,
+    js_sys::Reflect,
+    wasm_bindgen::{JsCast, JsValue},
+};
+
+/// Ways of writing to the clipboard, in the order they're tried
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CopyMethod {
+    ClipboardApi,
+    /// Selecting a hidden textarea and `execCommand("copy")`
+    ExecCommand,
+}
+
+/// Which methods to try, in order. The Clipboard API only exists in secure contexts; the
+/// `execCommand` fallback is always tried last, for plain-http origins, older browsers, and
+/// Clipboard API writes that are refused.
+fn copy_methods(has_clipboard_api: bool) -> &'static [CopyMethod] {
+    if has_clipboard_api {
+        &[CopyMethod::ClipboardApi, CopyMethod::ExecCommand]
+    } else {
+        &[CopyMethod::ExecCommand]
+    }
+}
+
+/// Copies `text` to the system clipboard, returning whether it worked, with the methods from
+/// [`copy_methods`]. When all fail, callers should show the text so it can be copied by hand.
+pub async fn copy_to_clipboard(text: &str) -> bool {
+    for method in copy_methods(has_clipboard_api()) {
+        let copied = match method {
+            CopyMethod::ClipboardApi => copy_with_clipboard_api(text).await,
+            CopyMethod::ExecCommand => copy_with_exec_command(text),
+        };
+
+        if copied {
+            return true;
+        }
+    }
+
+    false
+}
+
+fn has_clipboard_api() -> bool {
+    // Only exposed in secure contexts; calling through a missing one would throw
+    web_sys::window().is_some_and(|window| {
+        Reflect::get(&window.navigator(), &JsValue::from_str("clipboard"))
+            .is_ok_and(|clipboard| !clipboard.is_undefined())
+    })
+}
+
+async fn copy_with_clipboard_api(text: &str) -> bool {
+    let Some(window) = web_sys::window() else {
+        return false;
+    };
+
+    JsFuture::from(window.navigator().clipboard().write_text(text))
+        .await
+        .is_ok()
+}
+
+fn copy_with_exec_command(text: &str) -> bool {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return false;
+    };
+    let Some(body) = document.body() else {
+        return false;
+    };
+    let Ok(textarea) = document
+        .create_element("textarea")
+        .map(|e| e.unchecked_into::<HtmlTextAreaElement>())
+    else {
+        return false;
+    };
+
+    textarea.set_value(text);
+    textarea.set_read_only(true);
+    // In the page only long enough to be selected, and never visible
+    let _ = textarea.set_attribute("style", "position: fixed; top: 0; left: 0; opacity: 0;");
+
+    if body.append_child(&textarea).is_err() {
+        return false;
+    }
+
+    textarea.select();
+    let copied = document
+        .unchecked_ref::<HtmlDocument>()
+        .exec_command("copy")
+        .unwrap_or(false);
+    textarea.remove();
+
+    copied
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clipboard_api_is_tried_first_with_fallback() {
+        assert_eq!(
+            copy_methods(true),
+            [CopyMethod::ClipboardApi, CopyMethod::ExecCommand]
+        );
+    }
+
+    #[test]
+    fn without_clipboard_api_only_exec_command_is_tried() {
+        assert_eq!(copy_methods(false), [CopyMethod::ExecCommand]);
+    }
+}
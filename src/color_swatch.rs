@@ -0,0 +1,66 @@
+use leptos::prelude::*;
+
+use crate::product::FilamentColor;
+
+/// CSS background for a color's swatch
+fn swatch_background(color: &FilamentColor) -> &'static str {
+    match color {
+        FilamentColor::Transparent => "transparent",
+        FilamentColor::Multicolor => "linear-gradient(135deg, #FF0000, #FFFF00, #00FF00, #0000FF)",
+        c => c.hex(),
+    }
+}
+
+/// A row of color swatches to pick from. Each swatch is a button named after its color, so it
+/// can be reached with Tab and chosen with Enter or Space.
+#[component]
+pub fn ColorSwatchPicker(
+    #[prop(into)] colors: Signal<Vec<FilamentColor>>,
+    #[prop(into)] selected: Signal<Option<FilamentColor>>,
+    on_select: Callback<FilamentColor>,
+) -> impl IntoView {
+    view! {
+        <div class="swatches" role="group" aria-label="Colors">
+            <For
+                each=move || colors.get()
+                key=|c| c.to_string()
+                children=move |c: FilamentColor| {
+                    let name = c.to_string();
+                    let is_selected = {
+                        let c = c.clone();
+                        move || selected.with(|s| s.as_ref() == Some(&c))
+                    };
+
+                    view! {
+                        <button
+                            type="button"
+                            class="swatch"
+                            class:transparent=c == FilamentColor::Transparent
+                            class:selected=is_selected.clone()
+                            style=format!("background: {};", swatch_background(&c))
+                            title=name.clone()
+                            aria-label=name
+                            aria-pressed=move || is_selected().to_string()
+                            on:click=move |_| on_select.run(c.clone())
+                        ></button>
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+/// A small square of the color itself, shown before a color's name. The name stays in the normal
+/// text color, so every color reads the same on any background.
+#[component]
+pub fn ColorChip(color: FilamentColor) -> impl IntoView {
+    view! {
+        <span
+            class="color-chip"
+            class:transparent=color == FilamentColor::Transparent
+            class:glow=color == FilamentColor::GlowInTheDark
+            style=format!("background: {};", swatch_background(&color))
+            aria-hidden="true"
+        ></span>
+    }
+}
@@ -0,0 +1,70 @@
+use leptos::{ev, prelude::*};
+
+use crate::modal::Modal;
+
+/// Confirmation for destructive actions: the action button stays disabled until the admin types
+/// `expected` exactly (surrounding whitespace aside), like deleting a repository on GitHub
+#[component]
+pub fn TypedConfirm<F>(
+    open: RwSignal<bool>,
+    title: &'static str,
+    /// What will happen, shown above the input
+    #[prop(into)]
+    message: Signal<String>,
+    /// The text that has to be typed, e.g. a product name or a count
+    #[prop(into)]
+    expected: Signal<String>,
+    action_label: &'static str,
+    on_confirm: F,
+) -> impl IntoView
+where
+    F: Fn() + Copy + Send + Sync + 'static,
+{
+    let (typed, set_typed) = signal(String::new());
+
+    // Start empty each time, so a previous confirmation can't be reused
+    Effect::new(move |_| {
+        if open.get() {
+            set_typed.set(String::new());
+        }
+    });
+
+    let matches = move || {
+        let expected = expected.get();
+        !expected.trim().is_empty() && typed.get().trim() == expected.trim()
+    };
+
+    let on_submit = move |e: ev::SubmitEvent| {
+        e.prevent_default();
+
+        if matches() {
+            open.set(false);
+            on_confirm();
+        }
+    };
+
+    view! {
+        <Modal open=open title=title>
+            <form class="typed-confirm" on:submit=on_submit>
+                <p>{move || message.get()}</p>
+                <label for="typed-confirm-input">
+                    "Type " <strong>{move || expected.get()}</strong> " to confirm"
+                </label>
+                <input
+                    id="typed-confirm-input"
+                    class="input"
+                    type="text"
+                    autocomplete="off"
+                    prop:value=move || typed.get()
+                    on:input=move |e| set_typed.set(event_target_value(&e))
+                />
+                <div class="options-row">
+                    <button type="button" on:click=move |_| open.set(false)>"Cancel"</button>
+                    <button type="submit" class="danger" disabled=move || !matches()>
+                        {action_label}
+                    </button>
+                </div>
+            </form>
+        </Modal>
+    }
+}
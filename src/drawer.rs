@@ -0,0 +1,65 @@
+use leptos::{ev, html, prelude::*};
+use web_sys::{HtmlElement, wasm_bindgen::JsCast};
+
+/// Bottom sheet for controls that don't fit inline on a phone. Focus moves into the sheet when it
+/// opens and goes back to whatever opened it when it closes.
+#[component]
+pub fn Drawer(open: RwSignal<bool>, title: &'static str, children: ChildrenFn) -> impl IntoView {
+    let panel = NodeRef::<html::Div>::new();
+    let return_focus = StoredValue::new_local(None::<HtmlElement>);
+
+    let handle = window_event_listener(ev::keydown, move |e| {
+        if e.key() == "Escape" && open.get_untracked() {
+            open.set(false);
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    Effect::new(move |was_open: Option<bool>| {
+        let is_open = open.get();
+
+        if is_open && was_open != Some(true) {
+            return_focus.set_value(
+                document()
+                    .active_element()
+                    .and_then(|e| e.dyn_into::<HtmlElement>().ok()),
+            );
+            request_animation_frame(move || {
+                if let Some(panel) = panel.get_untracked() {
+                    let _ = panel.focus();
+                }
+            });
+        } else if !is_open
+            && was_open == Some(true)
+            && let Some(el) = return_focus.get_value()
+        {
+            let _ = el.focus();
+        }
+
+        is_open
+    });
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="drawer-backdrop" on:click=move |_| open.set(false)>
+                <div
+                    class="drawer"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label=title
+                    tabindex="-1"
+                    node_ref=panel
+                    on:click=|e| e.stop_propagation()
+                >
+                    <div class="modal-header">
+                        <h2>{title}</h2>
+                        <button class="modal-close" aria-label="Close" on:click=move |_| open.set(false)>
+                            "×"
+                        </button>
+                    </div>
+                    {children()}
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use serde::Serialize;
+
+use crate::{env::API_BASE_URL, request::ErrorResponse, session::Session};
+
+/// Most reports sent from one page load, so a render loop can't flood the backend
+const MAX_REPORTS_PER_PAGE: u32 = 5;
+/// Minimum gap between two reports
+const MIN_REPORT_INTERVAL_MS: f64 = 10_000.0;
+
+/// Mirrors the user's opt-in preference; read from the panic hook, which has no reactive context
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static SENT: Cell<u32> = const { Cell::new(0) };
+    static LAST_SENT_MS: Cell<f64> = const { Cell::new(f64::NEG_INFINITY) };
+}
+
+#[derive(Serialize)]
+struct ClientErrorReport {
+    kind: &'static str,
+    message: String,
+    route: String,
+    user_agent: String,
+    app_version: &'static str,
+}
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Logs panics to the console as before, then reports them if the user opted in
+pub fn install_panic_hook() {
+    std::panic::set_hook(Box::new(|info| {
+        console_error_panic_hook::hook(info);
+        report("panic", &info.to_string());
+    }));
+}
+
+/// Reports a failed API call. Client errors (4xx) are expected and not reported.
+pub fn report_api_error(path: &str, err: &ErrorResponse) {
+    if (400..500).contains(&err.status) {
+        return;
+    }
+
+    let path = path.split('?').next().unwrap_or_default();
+    report("api", &format!("{path} ({}): {}", err.status, err.message));
+}
+
+/// Sends a sanitized report to the backend, if the user opted in and the rate limit allows.
+/// Uses a beacon so it still goes out from a panicking or unloading page.
+fn report(kind: &'static str, message: &str) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+
+    let now = web_sys::js_sys::Date::now();
+
+    if SENT.get() >= MAX_REPORTS_PER_PAGE || now - LAST_SENT_MS.get() < MIN_REPORT_INTERVAL_MS {
+        return;
+    }
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let report = ClientErrorReport {
+        kind,
+        message: redact(message),
+        // Path only; query strings can carry search terms and one-off tokens
+        route: window.location().pathname().unwrap_or_default(),
+        user_agent: window.navigator().user_agent().unwrap_or_default(),
+        app_version: env!("CARGO_PKG_VERSION"),
+    };
+
+    let Ok(body) = serde_json::to_string(&report) else {
+        return;
+    };
+
+    SENT.set(SENT.get() + 1);
+    LAST_SENT_MS.set(now);
+
+    let _ = window
+        .navigator()
+        .send_beacon_with_opt_str(&format!("{API_BASE_URL}/client-errors"), Some(&body));
+}
+
+/// Strips the signed-in user's identity and tokens, email addresses, and anything JWT-shaped
+fn redact(message: &str) -> String {
+    let mut message = message.to_string();
+
+    if let Some(session) = Session::load() {
+        for (secret, placeholder) in [
+            (session.access_token, "[token]"),
+            (session.refresh_token, "[token]"),
+            (session.email, "[email]"),
+            (session.username, "[user]"),
+        ] {
+            if !secret.is_empty() {
+                message = message.replace(&secret, placeholder);
+            }
+        }
+    }
+
+    message
+        .split_inclusive(char::is_whitespace)
+        .map(|piece| {
+            let word = piece.trim_end();
+            let trailing = &piece[word.len()..];
+
+            if word.contains('@') && word.contains('.') {
+                format!("[email]{trailing}")
+            } else if looks_like_jwt(word) {
+                format!("[token]{trailing}")
+            } else {
+                piece.to_string()
+            }
+        })
+        .collect()
+}
+
+fn looks_like_jwt(word: &str) -> bool {
+    let word = word.trim_matches(|c: char| !c.is_ascii_alphanumeric());
+    let parts: Vec<&str> = word.split('.').collect();
+
+    parts.len() == 3
+        && word.len() > 20
+        && parts.iter().all(|p| {
+            !p.is_empty()
+                && p.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        })
+}
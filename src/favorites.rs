@@ -0,0 +1,190 @@
+use gloo_net::http::Method;
+use gloo_storage::{LocalStorage, Storage};
+use leptos::prelude::*;
+
+use crate::{
+    modal::Modal,
+    product::Product,
+    request::{Auth, request_json},
+    resource::{LoadState, use_resource},
+};
+
+const FAVORITES_KEY: &str = "favorites_v1";
+
+/// UUIDs of the products the visitor starred, in the order they were added. Kept in context so
+/// every star and the count stay in step; saved whenever it changes.
+#[derive(Clone, Copy)]
+pub struct Favorites(RwSignal<Vec<String>>);
+
+impl Favorites {
+    pub fn contains(&self, uuid: &str) -> bool {
+        self.0.with(|f| f.iter().any(|u| u == uuid))
+    }
+
+    pub fn toggle(&self, uuid: &str) {
+        self.0.update(|f| {
+            if let Some(i) = f.iter().position(|u| u == uuid) {
+                f.remove(i);
+            } else {
+                f.push(uuid.to_string());
+            }
+        });
+    }
+
+    pub fn count(&self) -> usize {
+        self.0.with(|f| f.len())
+    }
+
+    pub fn clear(&self) {
+        self.0.set(vec![]);
+    }
+}
+
+pub fn provide_favorites() {
+    let favorites =
+        RwSignal::new(LocalStorage::get::<Vec<String>>(FAVORITES_KEY).unwrap_or_default());
+
+    Effect::new(move |_| {
+        favorites.with(|f| {
+            let _ = LocalStorage::set(FAVORITES_KEY, f);
+        })
+    });
+
+    provide_context(Favorites(favorites));
+}
+
+pub fn use_favorites() -> Favorites {
+    use_context::<Favorites>().expect("favorites should be provided by App")
+}
+
+/// Star that adds a product to, or removes it from, the favorites
+#[component]
+pub fn FavoriteButton(uuid: String) -> impl IntoView {
+    let favorites = use_favorites();
+    let uuid = StoredValue::new(uuid);
+    let is_favorite = move || uuid.with_value(|u| favorites.contains(u));
+
+    let on_click = move |_| uuid.with_value(|u| favorites.toggle(u));
+
+    view! {
+        <button
+            class="favorite-button"
+            class:active=is_favorite
+            aria-pressed=move || is_favorite().to_string()
+            aria-label=move || if is_favorite() { "Remove from favorites" } else { "Add to favorites" }
+            on:click=on_click
+        >
+            {move || if is_favorite() { "★" } else { "☆" }}
+        </button>
+    }
+}
+
+/// Star with the number of favorites, linking to the favorites page
+#[component]
+pub fn FavoritesBadge() -> impl IntoView {
+    let favorites = use_favorites();
+
+    view! {
+        <a
+            href="/favorites"
+            class="favorites-badge"
+            aria-label=move || format!("Favorites ({})", favorites.count())
+        >
+            "★ " {move || favorites.count()}
+        </a>
+    }
+}
+
+#[component]
+pub fn FavoritesPage() -> impl IntoView {
+    let favorites = use_favorites();
+    let confirm_clear = RwSignal::new(false);
+
+    let products = use_resource(
+        move || Some(favorites.0.get()),
+        |uuids| async move {
+            let mut products = vec![];
+
+            for uuid in uuids {
+                let path = format!("products/{uuid}");
+
+                match request_json::<(), Product>(&path, Auth::Unauthorized, Method::GET, None)
+                    .await
+                {
+                    Ok(p) => products.push(p),
+                    // Removed from the catalog since it was starred
+                    Err(e) if e.status == 404 => (),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            Ok(products)
+        },
+    );
+
+    view! {
+        <div class="container">
+            <p><a href="/">"← Back to search"</a></p>
+            <div class="card">
+                <div class="options-row" style="justify-content: space-between;">
+                    <h1>"Favorites"</h1>
+                    <button
+                        class="danger"
+                        disabled=move || favorites.count() == 0
+                        on:click=move |_| confirm_clear.set(true)
+                    >
+                        "Clear all"
+                    </button>
+                </div>
+                {move || match products.get() {
+                    LoadState::Idle | LoadState::Loading => {
+                        view! { <div class="loading">"Loading…"</div> }.into_any()
+                    }
+                    LoadState::Failed(e) => {
+                        view! { <p class="error">{format!("Failed to load favorites: ({}) {}", e.status, e.message)}</p> }
+                            .into_any()
+                    }
+                    LoadState::Loaded(products) if products.is_empty() => {
+                        view! { <div class="empty">"Star products in the results to keep them here."</div> }
+                            .into_any()
+                    }
+                    LoadState::Loaded(products) => {
+                        view! {
+                            <ul class="favorites-list">
+                                {products
+                                    .into_iter()
+                                    .map(|p| {
+                                        view! {
+                                            <li>
+                                                <FavoriteButton uuid=p.uuid.clone() />
+                                                <a href=p.canonical_path()>{p.name.clone()}</a>
+                                                " — "
+                                                {p.price.format_in(p.currency)}
+                                            </li>
+                                        }
+                                    })
+                                    .collect_view()}
+                            </ul>
+                        }
+                            .into_any()
+                    }
+                }}
+            </div>
+            <Modal open=confirm_clear title="Clear favorites">
+                <p>{move || format!("Remove all {} favorites?", favorites.count())}</p>
+                <div class="options-row">
+                    <button on:click=move |_| confirm_clear.set(false)>"Cancel"</button>
+                    <button
+                        class="danger"
+                        on:click=move |_| {
+                            favorites.clear();
+                            confirm_clear.set(false);
+                        }
+                    >
+                        "Clear all"
+                    </button>
+                </div>
+            </Modal>
+        </div>
+    }
+}
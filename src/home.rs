@@ -1,15 +1,40 @@
 use leptos::prelude::*;
 
-use crate::{logout::LogoutButton, product_search::ProductSearch, session::Session};
+use crate::{
+    best_by_material::BestByMaterial, favorites::FavoritesBadge, logout::LogoutButton,
+    product_search::ProductSearch, session::Session,
+};
 
 #[component]
 pub fn HomePage() -> impl IntoView {
     let username: Option<String> = Session::load().map(|s| s.username);
+    // Set if the logo image fails to load, to show the name as text instead
+    let (logo_failed, set_logo_failed) = signal(false);
 
     view! {
         <div class="container">
+            <div style="text-align: right;">
+                <a href="/saved">"Saved searches"</a>
+                " · "
+                <FavoritesBadge />
+            </div>
             <div style="display: flex; justify-content: center;">
-                <img src="/public/filamentseek.png" alt="FilamentSeek Logo" style="height: 10em;" />
+                <a href="/" class="logo-link" aria-label="FilamentSeek home">
+                    <Show
+                        when=move || !logo_failed.get()
+                        fallback=|| view! { <span class="logo-wordmark">"FilamentSeek"</span> }
+                    >
+                        // Intrinsic size given so the space is reserved before the image loads
+                        <img
+                            class="logo"
+                            src="/public/filamentseek.png"
+                            alt="FilamentSeek Logo"
+                            width="200"
+                            height="200"
+                            on:error=move |_| set_logo_failed.set(true)
+                        />
+                    </Show>
+                </a>
             </div>
             <div class="card">
                 <ProductSearch />
@@ -22,6 +47,9 @@ pub fn HomePage() -> impl IntoView {
                     }
                 }
             </div>
+            <div class="card">
+                <BestByMaterial />
+            </div>
         </div>
     }
 }
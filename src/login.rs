@@ -1,12 +1,16 @@
 use gloo_net::http::Method;
-use leptos::{prelude::*, reactive::spawn_local};
+use leptos::{html, prelude::*, reactive::spawn_local};
 use serde::Serialize;
 
 use crate::{
+    product_search::focus_when_shown,
     request::{Auth, TokenResponse, request_json},
     session::Session,
 };
 
+/// `reason` query value for sending someone back to log in after their refresh token was rejected
+pub const SESSION_EXPIRED: &str = "session_expired";
+
 #[component]
 pub fn LoginPage() -> impl IntoView {
     view! {
@@ -35,6 +39,13 @@ pub fn LoginForm() -> impl IntoView {
     let (password, set_password) = signal(String::new());
     let (message, set_message) = signal(Option::<String>::None);
     let (loading, set_loading) = signal(false);
+    // Set once the backend asks for a two-factor code; the password is resent with it
+    let (mfa_step, set_mfa_step) = signal(false);
+    let (mfa_code, set_mfa_code) = signal(String::new());
+    let mfa_input = NodeRef::<html::Input>::new();
+
+    let params = leptos_router::hooks::use_query_map();
+    let session_expired = move || params.read().get("reason").as_deref() == Some(SESSION_EXPIRED);
 
     let on_submit = move |ev: leptos::ev::SubmitEvent| {
         ev.prevent_default();
@@ -45,12 +56,15 @@ pub fn LoginForm() -> impl IntoView {
             username: String,
             password: String,
             grant_type: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            mfa_code: Option<String>,
         }
 
         let body = LoginBody {
             username: username.get(),
             password: password.get(),
             grant_type: "password".to_string(),
+            mfa_code: mfa_step.get().then(|| mfa_code.get().trim().to_string()),
         };
 
         spawn_local(async move {
@@ -79,6 +93,17 @@ pub fn LoginForm() -> impl IntoView {
 
                     return;
                 }
+                Err(err) if err.is_mfa_required() && !mfa_step.get_untracked() => {
+                    set_message.set(None);
+                    set_mfa_step.set(true);
+                    focus_when_shown(mfa_input);
+                }
+                Err(err) if err.is_mfa_required() => {
+                    set_message.set(Some(
+                        "That code didn't work. Try the latest one.".to_string(),
+                    ));
+                    set_mfa_code.set(String::new());
+                }
                 Err(err) => {
                     set_message.set(Some(err.message));
                 }
@@ -91,30 +116,74 @@ pub fn LoginForm() -> impl IntoView {
     view! {
         <div>
             <form class="card" on:submit=on_submit>
-                <label>
-                    <span>"Username"</span>
-                    <input
-                        type="username"
-                        prop:value=move || username.get()
-                        on:input=move |e| set_username.set(event_target_value(&e))
-                        required
-                    />
-                </label>
-
-                <label>
-                    <span>"Password"</span>
-                    <input
-                        type="password"
-                        prop:value=move || password.get()
-                        on:input=move |e| set_password.set(event_target_value(&e))
-                        required
-                    />
-                </label>
+                <Show when=move || session_expired() && message.get().is_none()>
+                    <p class="price-note" role="status">
+                        "Your session expired, please sign in again."
+                    </p>
+                </Show>
+
+                <Show when=move || !mfa_step.get()>
+                    <label>
+                        <span>"Username"</span>
+                        <input
+                            type="username"
+                            prop:value=move || username.get()
+                            on:input=move |e| set_username.set(event_target_value(&e))
+                            required
+                        />
+                    </label>
+
+                    <label>
+                        <span>"Password"</span>
+                        <input
+                            type="password"
+                            prop:value=move || password.get()
+                            on:input=move |e| set_password.set(event_target_value(&e))
+                            required
+                        />
+                    </label>
+                </Show>
+
+                <Show when=move || mfa_step.get()>
+                    <label>
+                        <span>"Authentication code"</span>
+                        <input
+                            type="text"
+                            inputmode="numeric"
+                            autocomplete="one-time-code"
+                            pattern="[0-9]{6}"
+                            maxlength="6"
+                            node_ref=mfa_input
+                            prop:value=move || mfa_code.get()
+                            on:input=move |e| set_mfa_code.set(event_target_value(&e))
+                            required
+                        />
+                    </label>
+                    <small class="hint">"Enter the 6-digit code from your authenticator app"</small>
+                </Show>
 
                 <button type="submit" disabled=move || loading.get()>
-                    {move || if loading.get() { "Please wait…" } else { "Sign in" }}
+                    {move || match (loading.get(), mfa_step.get()) {
+                        (true, _) => "Please wait…",
+                        (false, true) => "Verify",
+                        (false, false) => "Sign in",
+                    }}
                 </button>
 
+                <Show when=move || mfa_step.get()>
+                    <button
+                        type="button"
+                        class="link-button"
+                        on:click=move |_| {
+                            set_mfa_step.set(false);
+                            set_mfa_code.set(String::new());
+                            set_message.set(None);
+                        }
+                    >
+                        "Use a different account"
+                    </button>
+                </Show>
+
                 <Show when=move || message.get().is_some()>
                     <p class="err">{move || message.get().unwrap_or_default()}</p>
                 </Show>
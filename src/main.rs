@@ -8,25 +8,72 @@ use login::LoginPage;
 use register::RegistrationPage;
 
 use crate::admin::AdminPage;
+use crate::favorites::{FavoritesPage, provide_favorites};
+use crate::preferences::{PreferencesPanel, provide_preferences, use_preferences};
+use crate::product_page::ProductPage;
+use crate::saved_searches::SavedSearchesPage;
+use crate::session::provide_session;
+use crate::shortcuts::ShortcutHelp;
+use crate::suggest::SuggestRetailer;
+use crate::taxonomy::provide_taxonomy;
+use crate::toast::{ToastHost, provide_toasts};
+use crate::viewport::provide_viewport_width;
 
 mod admin;
+mod admin_audit;
+mod admin_list;
+mod admin_quality;
+mod best_by_material;
+mod clipboard;
+mod color_swatch;
+mod confirm;
+mod drawer;
 mod env;
+mod error_report;
+mod favorites;
 mod home;
 mod login;
 mod logout;
+mod materials_legend;
+mod modal;
+mod preferences;
 mod product;
+mod product_page;
 mod product_search;
 mod register;
 mod request;
+mod resource;
+mod restock_alert;
+mod saved_searches;
 mod session;
+mod shortcuts;
+mod suggest;
+mod tag_input;
+mod taxonomy;
+mod toast;
+mod viewport;
 
 fn main() {
-    console_error_panic_hook::set_once();
+    error_report::install_panic_hook();
     leptos::mount::mount_to_body(App);
 }
 
 #[component]
 fn App() -> impl IntoView {
+    provide_session();
+    provide_preferences();
+    provide_taxonomy();
+    provide_viewport_width();
+    provide_toasts();
+    provide_favorites();
+
+    let prefs = use_preferences();
+    Effect::new(move |_| error_report::set_enabled(prefs.with(|p| p.report_errors)));
+
+    let show_shortcuts = RwSignal::new(false);
+    let show_preferences = RwSignal::new(false);
+    let show_suggest = RwSignal::new(false);
+
     view! {
         <Router>
             <Routes fallback=|| view! { <h1>"Not Found"</h1> }>
@@ -34,8 +81,47 @@ fn App() -> impl IntoView {
                 <Route path=path!("/register") view=RegistrationPage />
                 <Route path=path!("/login") view=LoginPage />
                 <Route path=path!("/admin") view=AdminPage />
+                <Route path=path!("/product/:uuid") view=ProductPage />
+                <Route path=path!("/product/:uuid/:slug") view=ProductPage />
+                <Route path=path!("/favorites") view=FavoritesPage />
+                <Route path=path!("/saved") view=SavedSearchesPage />
             </Routes>
         </Router>
+        <footer class="footer">
+            <a
+                href="#"
+                on:click=move |e| {
+                    e.prevent_default();
+                    show_shortcuts.set(true);
+                }
+            >
+                "⌨ shortcuts"
+            </a>
+            " · "
+            <a
+                href="#"
+                on:click=move |e| {
+                    e.prevent_default();
+                    show_preferences.set(true);
+                }
+            >
+                "⚙ preferences"
+            </a>
+            " · "
+            <a
+                href="#"
+                on:click=move |e| {
+                    e.prevent_default();
+                    show_suggest.set(true);
+                }
+            >
+                "+ suggest a retailer"
+            </a>
+        </footer>
+        <ShortcutHelp open=show_shortcuts />
+        <PreferencesPanel open=show_preferences />
+        <SuggestRetailer open=show_suggest />
+        <ToastHost />
     }
 }
 
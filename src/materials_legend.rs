@@ -0,0 +1,80 @@
+use leptos::prelude::*;
+use strum::IntoEnumIterator;
+
+use crate::{
+    product::{FilamentMaterial, PrintDifficulty},
+    taxonomy::use_taxonomy,
+};
+
+/// Key of known materials grouped by how hard they are to print. Clicking a material selects it.
+#[component]
+pub fn MaterialsLegend<F>(on_select: F) -> impl IntoView
+where
+    F: Fn(FilamentMaterial) + Copy + Send + Sync + 'static,
+{
+    let (open, set_open) = signal(false);
+    let taxonomy = use_taxonomy();
+
+    view! {
+        <div class="materials-legend">
+            <button
+                class="link-button"
+                aria-expanded=move || open.get().to_string()
+                on:click=move |_| set_open.update(|o| *o = !*o)
+            >
+                {move || if open.get() { "Hide material guide" } else { "Which material should I pick?" }}
+            </button>
+            <Show when=move || open.get()>
+                <div class="legend-tiers">
+                    {move || PrintDifficulty::iter()
+                        .map(|tier| {
+                            let materials: Vec<FilamentMaterial> = taxonomy.with(|t| {
+                                t.materials
+                                    .iter()
+                                    .filter(|m| m.properties().is_some_and(|p| p.difficulty == tier))
+                                    .cloned()
+                                    .collect()
+                            });
+
+                            view! {
+                                <div class="legend-tier">
+                                    <span class="legend-dot" style=format!("background: {};", tier.hex())></span>
+                                    <strong>{tier.to_string()}</strong>
+                                    {materials
+                                        .into_iter()
+                                        .map(|m| {
+                                            let props = m.properties();
+                                            let title = props
+                                                .map(|p| {
+                                                    format!(
+                                                        "Nozzle {}–{} °C, bed {}–{} °C",
+                                                        p.nozzle_temp_c.0,
+                                                        p.nozzle_temp_c.1,
+                                                        p.bed_temp_c.0,
+                                                        p.bed_temp_c.1,
+                                                    )
+                                                })
+                                                .unwrap_or_default();
+                                            let label = m.to_string();
+
+                                            view! {
+                                                <button
+                                                    class="legend-material"
+                                                    title=title
+                                                    style=format!("border-color: {};", tier.hex())
+                                                    on:click=move |_| on_select(m.clone())
+                                                >
+                                                    {label}
+                                                </button>
+                                            }
+                                        })
+                                        .collect_view()}
+                                </div>
+                            }
+                        })
+                        .collect_view()}
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,33 @@
+use leptos::{ev, prelude::*};
+
+#[component]
+pub fn Modal(open: RwSignal<bool>, title: &'static str, children: ChildrenFn) -> impl IntoView {
+    let handle = window_event_listener(ev::keydown, move |e| {
+        if e.key() == "Escape" && open.get_untracked() {
+            open.set(false);
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    view! {
+        <Show when=move || open.get()>
+            <div class="modal-backdrop" on:click=move |_| open.set(false)>
+                <div
+                    class="modal"
+                    role="dialog"
+                    aria-modal="true"
+                    aria-label=title
+                    on:click=|e| e.stop_propagation()
+                >
+                    <div class="modal-header">
+                        <h2>{title}</h2>
+                        <button class="modal-close" aria-label="Close" on:click=move |_| open.set(false)>
+                            "×"
+                        </button>
+                    </div>
+                    {children()}
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,327 @@
+use gloo_storage::{LocalStorage, Storage};
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use strum_macros::EnumIter;
+use web_sys::js_sys::encode_uri_component;
+
+use crate::{
+    modal::Modal,
+    product::{Currency, Region},
+    product_search::RegionMode,
+};
+
+/// Every client-side preference lives under this one key
+const PREFERENCES_KEY: &str = "preferences";
+/// Bump this and add a step to `migrate` whenever a stored field is renamed or reinterpreted
+const PREFERENCES_VERSION: u32 = 1;
+
+/// How tightly the results table is laid out
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Density {
+    #[default]
+    Comfortable,
+    /// The narrow-screen layout, regardless of screen width
+    Compact,
+}
+
+/// A results column that can be hidden. The name column is always shown.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum TableColumn {
+    Price,
+    PricePerKg,
+    PricePerMeter,
+    Material,
+    Color,
+    Diameter,
+    Weight,
+    Retailer,
+}
+
+impl TableColumn {
+    /// The header text, with prices per unit in `currency`, e.g. "€ / kg"
+    pub fn label(&self, currency: Currency) -> String {
+        match self {
+            TableColumn::Price => "Price".to_string(),
+            TableColumn::PricePerKg => format!("{} / kg", currency.symbol()),
+            TableColumn::PricePerMeter => format!("{} / m", currency.symbol()),
+            TableColumn::Material => "Material".to_string(),
+            TableColumn::Color => "Color".to_string(),
+            TableColumn::Diameter => "Diameter".to_string(),
+            TableColumn::Weight => "Weight".to_string(),
+            TableColumn::Retailer => "Retailer".to_string(),
+        }
+    }
+
+    fn is_price(&self) -> bool {
+        matches!(self, TableColumn::Price | TableColumn::PricePerKg)
+    }
+}
+
+/// User preferences that persist across visits and can be carried to another device
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Preferences {
+    pub open_links_in_new_tab: bool,
+    pub density: Density,
+    /// Whether the results table shows "$19" rather than "$19.00"
+    pub compact_prices: bool,
+    /// Whether the compact results layout rounds $/kg to whole dollars, e.g. "$22/kg"
+    pub round_compact_price_per_kg: bool,
+    /// Whether the price statistics strip is shown above the results. Searches only ask the
+    /// backend for full-result statistics while it is.
+    pub show_stats: bool,
+    /// Where the user wants things shipped; guessed from the browser language until chosen
+    pub region: Option<Region>,
+    pub region_mode: RegionMode,
+    /// Whether listings that don't say where they ship are hidden when filtering by region
+    pub hide_unknown_region: bool,
+    /// How often a watched search is re-run, in minutes
+    pub watch_minutes: u32,
+    /// Whether crashes and failed requests are reported (sanitized) to help fix them
+    pub report_errors: bool,
+    /// Results columns the user turned off. Stored this way round so columns added later show up.
+    pub hidden_columns: Vec<TableColumn>,
+}
+
+impl Default for Preferences {
+    fn default() -> Self {
+        Self {
+            open_links_in_new_tab: true,
+            density: Density::Comfortable,
+            compact_prices: false,
+            round_compact_price_per_kg: false,
+            show_stats: true,
+            region: browser_region(),
+            region_mode: RegionMode::Anywhere,
+            hide_unknown_region: false,
+            watch_minutes: 5,
+            report_errors: false,
+            hidden_columns: vec![],
+        }
+    }
+}
+
+fn browser_region() -> Option<Region> {
+    let locale = web_sys::window()?.navigator().language()?;
+    Region::from_locale(&locale)
+}
+
+/// On-disk (and export) format, tagged with the version it was written with
+#[derive(Serialize, Deserialize)]
+struct StoredPreferences {
+    version: u32,
+    preferences: Value,
+}
+
+impl Preferences {
+    pub fn shows_column(&self, column: TableColumn) -> bool {
+        // $/kg comes back if an imported file hid both price columns
+        !self.hidden_columns.contains(&column)
+            || (column == TableColumn::PricePerKg
+                && self.hidden_columns.contains(&TableColumn::Price))
+    }
+
+    /// Shows or hides `column`, refusing to hide the last visible price column
+    pub fn set_column_shown(&mut self, column: TableColumn, shown: bool) {
+        if shown {
+            self.hidden_columns.retain(|c| *c != column);
+        } else if self.can_hide_column(column) && self.shows_column(column) {
+            self.hidden_columns.push(column);
+        }
+    }
+
+    pub fn can_hide_column(&self, column: TableColumn) -> bool {
+        !column.is_price()
+            || [TableColumn::Price, TableColumn::PricePerKg]
+                .into_iter()
+                .any(|c| c != column && self.shows_column(c))
+    }
+
+    pub fn load() -> Self {
+        LocalStorage::get::<StoredPreferences>(PREFERENCES_KEY)
+            .ok()
+            .and_then(|stored| migrate(stored).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        let stored = StoredPreferences {
+            version: PREFERENCES_VERSION,
+            preferences: serde_json::to_value(self).unwrap_or(Value::Null),
+        };
+        let _ = LocalStorage::set(PREFERENCES_KEY, &stored);
+    }
+
+    pub fn export_json(&self) -> String {
+        let stored = StoredPreferences {
+            version: PREFERENCES_VERSION,
+            preferences: serde_json::to_value(self).unwrap_or(Value::Null),
+        };
+        serde_json::to_string_pretty(&stored).unwrap_or_default()
+    }
+
+    pub fn import_json(json: &str) -> Result<Self, String> {
+        let stored = serde_json::from_str::<StoredPreferences>(json)
+            .map_err(|e| format!("Not a preferences export: {e}"))?;
+        migrate(stored)
+    }
+}
+
+/// Upgrades preferences written by an older version of the app to the current shape
+fn migrate(stored: StoredPreferences) -> Result<Preferences, String> {
+    match stored.version {
+        PREFERENCES_VERSION => serde_json::from_value(stored.preferences)
+            .map_err(|e| format!("Invalid preferences: {e}")),
+        v => Err(format!(
+            "Preferences version {v} is newer than this app supports ({PREFERENCES_VERSION})"
+        )),
+    }
+}
+
+/// Loads preferences into a signal shared through context, saving them whenever they change
+pub fn provide_preferences() {
+    let prefs = RwSignal::new(Preferences::load());
+
+    Effect::new(move |_| prefs.with(|p| p.save()));
+
+    provide_context(prefs);
+}
+
+pub fn use_preferences() -> RwSignal<Preferences> {
+    use_context::<RwSignal<Preferences>>().expect("preferences should be provided by App")
+}
+
+#[component]
+pub fn PreferencesPanel(open: RwSignal<bool>) -> impl IntoView {
+    let prefs = use_preferences();
+    let (import_text, set_import_text) = signal(String::new());
+    let (message, set_message) = signal::<Option<Result<String, String>>>(None);
+
+    let download_href = move || {
+        format!(
+            "data:application/json;charset=utf-8,{}",
+            encode_uri_component(&prefs.with(|p| p.export_json()))
+        )
+    };
+
+    let on_import = move |_| match Preferences::import_json(&import_text.get_untracked()) {
+        Ok(imported) => {
+            prefs.set(imported);
+            set_import_text.set(String::new());
+            set_message.set(Some(Ok("Preferences imported".to_string())));
+        }
+        Err(e) => set_message.set(Some(Err(e))),
+    };
+
+    let on_reset = move |_| {
+        prefs.set(Preferences::default());
+        set_message.set(Some(Ok("Preferences reset to defaults".to_string())));
+    };
+
+    view! {
+        <Modal open=open title="Preferences">
+            <section style="display: grid; gap: 12px; margin-top: 12px;">
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.open_links_in_new_tab)
+                        on:change=move |e| {
+                            let checked = event_target_checked(&e);
+                            prefs.update(|p| p.open_links_in_new_tab = checked);
+                        }
+                    />
+                    "Open retailer links in a new tab"
+                </label>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.density == Density::Compact)
+                        on:change=move |e| {
+                            let density = if event_target_checked(&e) {
+                                Density::Compact
+                            } else {
+                                Density::Comfortable
+                            };
+                            prefs.update(|p| p.density = density);
+                        }
+                    />
+                    "Compact results table"
+                </label>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.compact_prices)
+                        on:change=move |e| {
+                            let checked = event_target_checked(&e);
+                            prefs.update(|p| p.compact_prices = checked);
+                        }
+                    />
+                    "Hide cents on whole-dollar prices in results"
+                </label>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.round_compact_price_per_kg)
+                        on:change=move |e| {
+                            let checked = event_target_checked(&e);
+                            prefs.update(|p| p.round_compact_price_per_kg = checked);
+                        }
+                    />
+                    "Round $/kg to whole dollars in the compact layout"
+                </label>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.show_stats)
+                        on:change=move |e| {
+                            let checked = event_target_checked(&e);
+                            prefs.update(|p| p.show_stats = checked);
+                        }
+                    />
+                    "Show price statistics above results"
+                </label>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.report_errors)
+                        on:change=move |e| {
+                            let checked = event_target_checked(&e);
+                            prefs.update(|p| p.report_errors = checked);
+                        }
+                    />
+                    "Send error reports (no tokens or personal details)"
+                </label>
+
+                <h3>"Export"</h3>
+                <textarea class="input" rows="5" readonly prop:value=move || prefs.with(|p| p.export_json())></textarea>
+                <a href=download_href download="filamentseek-preferences.json">"Download as file"</a>
+
+                <h3>"Import"</h3>
+                <textarea
+                    class="input"
+                    rows="5"
+                    placeholder="Paste an exported preferences file here"
+                    prop:value=move || import_text.get()
+                    on:input=move |e| set_import_text.set(event_target_value(&e))
+                ></textarea>
+                <button disabled=move || import_text.with(|t| t.trim().is_empty()) on:click=on_import>
+                    "Import"
+                </button>
+
+                <button class="danger" on:click=on_reset>"Reset all to defaults"</button>
+
+                {move || {
+                    message
+                        .get()
+                        .map(|m| match m {
+                            Ok(s) => view! { <p class="success">{s}</p> }.into_any(),
+                            Err(s) => view! { <p class="error">{s}</p> }.into_any(),
+                        })
+                }}
+            </section>
+        </Modal>
+    }
+}
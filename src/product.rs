@@ -3,14 +3,39 @@ use std::{
     fmt::{self, Display},
     str::FromStr,
 };
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
+/// Prices last checked longer ago than this are flagged as possibly out of date
+pub const STALE_PRICE_DAYS: u32 = 7;
+
+/// Longest free-text material or color name accepted; longer ones break the table layout and
+/// bloat shared search URLs
+pub const MAX_OTHER_LEN: usize = 64;
+
+/// Longest description a product may have, in characters
+pub const MAX_DESCRIPTION_LEN: usize = 500;
+/// Characters of the description shown in a result row's tooltip
+const DESCRIPTION_PREVIEW_LEN: usize = 120;
+
+/// Trims a free-text material or color name and cuts it to [`MAX_OTHER_LEN`] characters
+pub fn clamp_other(s: &str) -> String {
+    let clamped = s.trim().chars().take(MAX_OTHER_LEN).collect::<String>();
+    clamped.trim_end().to_string()
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Product {
     pub uuid: String,
     pub name: String,
     pub price: Cents,
+    /// The retailer's regular price, when `price` is a sale price
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_price: Option<Cents>,
     pub price_per_kg: Cents,
+    /// What `price`, `list_price` and `price_per_kg` are in
+    #[serde(default)]
+    pub currency: Currency,
     pub url: String,
     pub material: FilamentMaterial,
     pub diameter: FilamentDiameter,
@@ -18,24 +43,529 @@ pub struct Product {
     pub retailer: Retailer,
     pub retailer_product_id: String,
     pub color: FilamentColor,
+    /// Whether `weight` is the filament alone or includes the spool
+    #[serde(default)]
+    pub weight_basis: WeightBasis,
+    /// Weight of the empty spool, when known, so a gross weight can be converted to net
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub empty_spool_weight: Option<Grams>,
+    /// Whether the retailer has the listing in stock; `None` when unknown
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub in_stock: Option<bool>,
+    /// Regions the listing ships to; empty when unknown
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ships_to: Vec<Region>,
+    /// Freeform labels such as "silk" or "refill", normalized by [`normalize_tag`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nozzle_temp: Option<TemperatureSpec>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bed_temp: Option<TemperatureSpec>,
+    /// A short note about the listing, e.g. "high-flow, matte finish"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// When the product was last modified (RFC 3339), as recorded by the backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Username of the admin who last modified the product
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_by: Option<String>,
+    /// When the price was last checked at the retailer (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub price_updated_at: Option<String>,
+}
+
+impl Product {
+    /// Whether the listing ships to `region`, or `None` when its regions are unknown
+    pub fn ships_to_region(&self, region: Region) -> Option<bool> {
+        (!self.ships_to.is_empty()).then(|| self.ships_to.contains(&region))
+    }
+
+    /// The discount off the list price, if the product is on sale
+    pub fn discount(&self) -> Option<Discount> {
+        let list_price = self.list_price?;
+
+        if list_price <= self.price || list_price.0 == 0 {
+            return None;
+        }
+
+        let percent = (list_price.0 - self.price.0) as f32 / list_price.0 as f32 * 100.0;
+        Some(Discount(percent.round() as u32))
+    }
+
+    /// Seconds since the price was last checked, or `None` when unknown
+    pub fn price_age_secs(&self) -> Option<f64> {
+        let updated_ms = web_sys::js_sys::Date::parse(self.price_updated_at.as_deref()?);
+
+        if updated_ms.is_nan() {
+            return None;
+        }
+
+        Some((crate::session::server_now_secs() - updated_ms / 1000.0).max(0.0))
+    }
+
+    /// Whether the price is old enough that it may no longer be accurate
+    pub fn price_is_stale(&self) -> bool {
+        self.price_age_secs()
+            .is_some_and(|age| age > STALE_PRICE_DAYS as f64 * 86_400.0)
+    }
+
+    /// The start of the description, cut at a word boundary for a tooltip
+    pub fn description_preview(&self) -> Option<String> {
+        let description = self.description.as_deref()?.trim();
+
+        if description.chars().count() <= DESCRIPTION_PREVIEW_LEN {
+            return (!description.is_empty()).then(|| description.to_string());
+        }
+
+        let cut: String = description.chars().take(DESCRIPTION_PREVIEW_LEN).collect();
+        let cut = cut.rsplit_once(' ').map_or(cut.as_str(), |(head, _)| head);
+        Some(format!("{}…", cut.trim_end()))
+    }
+
+    /// Checks the rules every saved product must satisfy, returning all problems found
+    pub fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let mut errors = vec![];
+        let mut fail = |field, message: &str| {
+            errors.push(FieldError {
+                field,
+                message: message.to_string(),
+            })
+        };
+
+        if self.name.trim().is_empty() {
+            fail(ProductField::Name, "Name is required");
+        }
+
+        if self.price.0 == 0 {
+            fail(ProductField::Price, "Price must be greater than zero");
+        }
+
+        if self.weight.0 == 0 {
+            fail(ProductField::Weight, "Weight must be greater than zero");
+        }
+
+        if self.diameter != FilamentDiameter::Unspecified
+            && !(MIN_DIAMETER_H..=MAX_DIAMETER_H).contains(&self.diameter.hundredths())
+        {
+            fail(
+                ProductField::Diameter,
+                "Diameter must be between 1 and 4 mm",
+            );
+        }
+
+        if !is_well_formed_url(&self.url) {
+            fail(ProductField::Url, "URL must be a full http(s):// address");
+        }
+
+        if let FilamentMaterial::Other(s) = &self.material
+            && s.chars().count() > MAX_OTHER_LEN
+        {
+            fail(
+                ProductField::Material,
+                &format!("Material name must be at most {MAX_OTHER_LEN} characters"),
+            );
+        }
+
+        if self
+            .description
+            .as_ref()
+            .is_some_and(|d| d.chars().count() > MAX_DESCRIPTION_LEN)
+        {
+            fail(
+                ProductField::Description,
+                &format!("Description must be at most {MAX_DESCRIPTION_LEN} characters"),
+            );
+        }
+
+        if let FilamentColor::Other(s) = &self.color
+            && s.chars().count() > MAX_OTHER_LEN
+        {
+            fail(
+                ProductField::Color,
+                &format!("Color name must be at most {MAX_OTHER_LEN} characters"),
+            );
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// The $/kg to show, or `None` when it can't be trusted because the weight is unknown or
+    /// the backend reported zero for a product that isn't free
+    pub fn displayable_price_per_kg(&self) -> Option<Cents> {
+        if self.weight.0 == 0 || (self.price_per_kg.0 == 0 && self.price.0 != 0) {
+            return None;
+        }
+
+        Some(self.price_per_kg)
+    }
+
+    /// Cost of one meter of filament in dollars, from the material's density and the diameter.
+    /// Uses the net $/kg when the listed weight includes the spool.
+    pub fn dollars_per_meter(&self) -> Option<f64> {
+        let density = self.material.density_g_cm3()? as f64;
+        let mm = self.diameter.mm() as f64;
+        if mm <= 0.0 {
+            return None;
+        }
+
+        let ppk = self
+            .net_price_per_kg()
+            .or_else(|| self.displayable_price_per_kg())?;
+        // Cross-section in mm² times 1000 mm is mm³ per meter; / 1000 makes it cm³
+        let radius = mm / 2.0;
+        let grams_per_meter = density * std::f64::consts::PI * radius * radius;
+
+        Some(ppk.0 as f64 / 100.0 * grams_per_meter / 1000.0)
+    }
+
+    /// What `price_per_kg` should be given the current price and weight, if the weight is known
+    pub fn expected_price_per_kg(&self) -> Option<Cents> {
+        if self.weight.0 == 0 {
+            return None;
+        }
+
+        let cents = self.price.0 as f64 * 1000.0 / self.weight.0 as f64;
+        Some(Cents(cents.round() as u32))
+    }
+
+    /// The weight of filament alone, if the listing's basis (and spool weight) make it known
+    pub fn net_weight(&self) -> Option<Grams> {
+        match self.weight_basis {
+            WeightBasis::NetFilament => (self.weight.0 != 0).then_some(self.weight),
+            WeightBasis::GrossWithSpool => self
+                .empty_spool_weight
+                .filter(|spool| spool.0 < self.weight.0)
+                .map(|spool| Grams(self.weight.0 - spool.0)),
+            WeightBasis::Unknown => None,
+        }
+    }
+
+    /// $/kg of filament alone, for listings whose weight includes the spool and whose spool
+    /// weight is known; otherwise `None` and `price_per_kg` already stands
+    pub fn net_price_per_kg(&self) -> Option<Cents> {
+        if self.weight_basis != WeightBasis::GrossWithSpool {
+            return None;
+        }
+
+        let net = self.net_weight()?;
+        let cents = self.price.0 as f64 * 1000.0 / net.0 as f64;
+        Some(Cents(cents.round() as u32))
+    }
+
+    /// The weight with its basis, e.g. "1 kg (net)"
+    pub fn weight_label(&self) -> String {
+        match self.weight_basis {
+            WeightBasis::Unknown => self.weight.to_string(),
+            basis => format!("{} ({})", self.weight, basis.short_label()),
+        }
+    }
+
+    /// URL-friendly form of the product's name, e.g. "polymaker-pla-black-1kg"
+    pub fn slug(&self) -> String {
+        slugify(&self.name)
+    }
+
+    /// The canonical path of the product's page, with its slug when the name yields one
+    pub fn canonical_path(&self) -> String {
+        match self.slug() {
+            slug if slug.is_empty() => format!("/product/{}", self.uuid),
+            slug => format!("/product/{}/{slug}", self.uuid),
+        }
+    }
+
+    /// Whether the stored `price_per_kg` disagrees with `price`/`weight` by more than rounding
+    pub fn price_per_kg_is_stale(&self) -> bool {
+        self.expected_price_per_kg()
+            .is_some_and(|expected| expected.0.abs_diff(self.price_per_kg.0) > 1)
+    }
+}
+
+/// Narrowest and widest filament a product may list, in hundredths of a millimeter
+pub const MIN_DIAMETER_H: u16 = 100;
+pub const MAX_DIAMETER_H: u16 = 400;
+
+/// The product fields that [`Product::validate`] checks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProductField {
+    Name,
+    Price,
+    Weight,
+    Diameter,
+    Url,
+    Material,
+    Color,
+    Description,
+}
+
+impl ProductField {
+    /// The field for a serialized `Product` field name, as used in the backend's validation errors
+    pub fn from_api_name(name: &str) -> Option<Self> {
+        match name {
+            "name" => Some(ProductField::Name),
+            "price" => Some(ProductField::Price),
+            "weight" => Some(ProductField::Weight),
+            "diameter" => Some(ProductField::Diameter),
+            "url" => Some(ProductField::Url),
+            "material" => Some(ProductField::Material),
+            "color" => Some(ProductField::Color),
+            "description" => Some(ProductField::Description),
+            _ => None,
+        }
+    }
+}
+
+/// A problem with one field of a product
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldError {
+    pub field: ProductField,
+    pub message: String,
+}
+
+/// Whether `url` is an absolute http(s) URL with a plausible host
+pub fn is_well_formed_url(url: &str) -> bool {
+    let url = url.trim();
+
+    if url.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    let Some((scheme, _)) = url.split_once("://") else {
+        return false;
+    };
+
+    matches!(scheme.to_lowercase().as_str(), "http" | "https")
+        && url_host(url).is_some_and(|h| h.contains('.'))
+}
+
+/// Trims a product name and collapses runs of whitespace inside it to single spaces
+pub fn normalize_name(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Query parameters that only record where a visitor came from, stripped from every URL.
+/// A trailing `*` matches any parameter with that prefix.
+const COMMON_TRACKING_PARAMS: &[&str] = &["utm_*", "fbclid", "gclid", "msclkid", "mc_eid"];
+
+/// Trims a product URL and removes tracking query parameters, both the common ones and
+/// those listed for `retailer`. Everything else, including the fragment, is kept.
+pub fn normalize_url(url: &str, retailer: &Retailer) -> String {
+    let url = url.trim();
+    let (url, fragment) = match url.split_once('#') {
+        Some((u, f)) => (u, Some(f)),
+        None => (url, None),
+    };
+    let (base, query) = url.split_once('?').unwrap_or((url, ""));
+
+    let is_tracking = |key: &str| {
+        COMMON_TRACKING_PARAMS
+            .iter()
+            .chain(retailer.tracking_params())
+            .any(|pattern| match pattern.strip_suffix('*') {
+                Some(prefix) => key.starts_with(prefix),
+                None => key == *pattern,
+            })
+    };
+
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| !is_tracking(pair.split('=').next().unwrap_or_default()))
+        .collect();
+
+    let mut out = base.to_string();
+    if !kept.is_empty() {
+        out.push('?');
+        out.push_str(&kept.join("&"));
+    }
+    if let Some(fragment) = fragment {
+        out.push('#');
+        out.push_str(fragment);
+    }
+    out
+}
+
+/// Lowercases `text` and joins its runs of ASCII letters and digits with single hyphens
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// An age in seconds as shown to shoppers, e.g. "just now", "2h ago" or "3 days ago"
+pub fn humanize_age(secs: f64) -> String {
+    let minutes = (secs / 60.0).floor() as u64;
+    let hours = minutes / 60;
+    let days = hours / 24;
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => "just now".to_string(),
+        (0, 0, m) => format!("{m}m ago"),
+        (0, h, _) => format!("{h}h ago"),
+        (1, _, _) => "1 day ago".to_string(),
+        (d, _, _) => format!("{d} days ago"),
+    }
+}
+
+/// Lowercases and trims a tag as typed, or `None` if nothing is left
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().to_lowercase();
+    (!tag.is_empty()).then_some(tag)
+}
+
+/// A percentage off the list price
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Discount(pub u32);
+
+impl Display for Discount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}% off", self.0)
+    }
+}
+
+/// The currency a retailer quotes prices in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter)]
+#[serde(rename_all = "lowercase")]
+pub enum Currency {
+    #[default]
+    Usd,
+    Eur,
+    Gbp,
+}
+
+impl Currency {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Currency::Usd => "$",
+            Currency::Eur => "€",
+            Currency::Gbp => "£",
+        }
+    }
+
+    /// The code used in URLs, e.g. "eur"
+    pub fn code(&self) -> &'static str {
+        match self {
+            Currency::Usd => "usd",
+            Currency::Eur => "eur",
+            Currency::Gbp => "gbp",
+        }
+    }
 }
 
+impl Display for Currency {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code().to_uppercase())
+    }
+}
+
+impl FromStr for Currency {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Currency::iter()
+            .find(|c| c.code().eq_ignore_ascii_case(s.trim()))
+            .ok_or(())
+    }
+}
+
+/// An amount in hundredths of its currency. `Display` shows dollars; use [`Cents::format_in`]
+/// for a product's own currency.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Cents(pub u32);
 
 impl Cents {
-    pub fn as_dollars(&self) -> f32 {
-        self.0 as f32 / 100.0
+    /// Rounds a dollar amount to the nearest cent. Negative and non-finite amounts give
+    /// `Cents(0)`; amounts too large to represent give the largest.
+    pub fn from_dollars(dollars: f32) -> Self {
+        if !dollars.is_finite() || dollars <= 0.0 {
+            return Cents(0);
+        }
+
+        let cents = (f64::from(dollars) * 100.0).round();
+        Cents(cents.min(f64::from(u32::MAX)) as u32)
+    }
+
+    /// Parses an amount as typed, e.g. "19.99", "$1,299", "€19,99" or "£12". Extra decimals round
+    /// to the nearest cent; `None` if empty, negative or not a number.
+    pub fn from_dollars_str(s: &str) -> Option<Self> {
+        let s = s.trim();
+        let s = Currency::iter()
+            .find_map(|c| s.strip_prefix(c.symbol()))
+            .unwrap_or(s)
+            .trim_start();
+
+        // A comma with one or two digits after it is a decimal comma; any other is a separator
+        let normalized = match s.rsplit_once(',') {
+            Some((whole, frac))
+                if !s.contains('.') && (1..=2).contains(&frac.len()) && !whole.contains(',') =>
+            {
+                format!("{whole}.{frac}")
+            }
+            _ => s.replace(',', ""),
+        };
+
+        if normalized.is_empty() || !normalized.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return None;
+        }
+
+        let cents = (normalized.parse::<f64>().ok()? * 100.0).round();
+        (cents <= u32::MAX as f64).then_some(Cents(cents as u32))
+    }
+
+    /// The amount as it is typed into a dollar input, e.g. "19.99"
+    pub fn to_dollars_str(self) -> String {
+        format!("{}.{:02}", self.0 / 100, self.0 % 100)
+    }
+
+    /// The amount for exports, e.g. "1299.00": always a `.` separator, no currency symbol and no
+    /// grouping, whatever the display settings, so spreadsheets import it as a number
+    pub fn to_plain_decimal(self) -> String {
+        self.to_dollars_str()
+    }
+
+    /// Whole dollars as cents, or `None` if the amount doesn't fit
+    pub fn from_whole_dollars(dollars: u32) -> Option<Self> {
+        dollars.checked_mul(100).map(Cents)
+    }
+
+    /// Scales the amount by `percent` (e.g. -10.0 for a 10% discount), never going below zero
+    pub fn adjusted_by_percent(&self, percent: f32) -> Self {
+        let adjusted = (self.0 as f32 * (1.0 + percent / 100.0)).round();
+        Cents(adjusted.max(0.0) as u32)
+    }
+}
+
+impl Cents {
+    /// The amount with `currency`'s symbol, e.g. "€19.99"
+    pub fn format_in(&self, currency: Currency) -> String {
+        format!("{}{}.{:02}", currency.symbol(), self.0 / 100, self.0 % 100)
     }
 
-    pub fn from_dollars(dollars: f32) -> Self {
-        Cents((dollars * 100.0).round() as u32)
+    /// Like [`Cents::format_in`], but whole amounts drop their cents: "$19" rather than "$19.00"
+    pub fn format_compact(&self, currency: Currency) -> String {
+        if self.0.is_multiple_of(100) {
+            format!("{}{}", currency.symbol(), self.0 / 100)
+        } else {
+            self.format_in(currency)
+        }
+    }
+
+    /// Rounded to the nearest whole unit, e.g. "$22" for $21.50
+    pub fn format_whole_dollars(&self, currency: Currency) -> String {
+        format!("{}{}", currency.symbol(), self.0.saturating_add(50) / 100)
     }
 }
 
 impl Display for Cents {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "${:.2}", self.as_dollars())
+        write!(f, "{}", self.format_in(Currency::Usd))
     }
 }
 
@@ -118,6 +648,140 @@ impl Display for FilamentMaterial {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+pub enum PrintDifficulty {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Display for PrintDifficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrintDifficulty::Easy => write!(f, "Easy"),
+            PrintDifficulty::Medium => write!(f, "Medium"),
+            PrintDifficulty::Hard => write!(f, "Hard"),
+        }
+    }
+}
+
+impl PrintDifficulty {
+    pub fn hex(&self) -> &'static str {
+        match self {
+            PrintDifficulty::Easy => "#4CAF50",
+            PrintDifficulty::Medium => "#E0A84F",
+            PrintDifficulty::Hard => "#E85C5C",
+        }
+    }
+}
+
+/// Typical printing characteristics of a material; temperatures are ranges in °C
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaterialProperties {
+    pub difficulty: PrintDifficulty,
+    pub nozzle_temp_c: (u16, u16),
+    pub bed_temp_c: (u16, u16),
+}
+
+impl FilamentMaterial {
+    /// Typical density in g/cm³, used to turn weight into length
+    pub fn density_g_cm3(&self) -> Option<f32> {
+        match self {
+            FilamentMaterial::PLA | FilamentMaterial::PLAPlus => Some(1.24),
+            FilamentMaterial::PETG => Some(1.27),
+            FilamentMaterial::PCTG => Some(1.23),
+            FilamentMaterial::TPU => Some(1.21),
+            FilamentMaterial::ABS => Some(1.04),
+            FilamentMaterial::ASA => Some(1.07),
+            FilamentMaterial::Nylon => Some(1.14),
+            FilamentMaterial::PC => Some(1.20),
+            FilamentMaterial::Unspecified | FilamentMaterial::Other(_) => None,
+        }
+    }
+
+    pub fn properties(&self) -> Option<MaterialProperties> {
+        let (difficulty, nozzle_temp_c, bed_temp_c) = match self {
+            FilamentMaterial::PLA => (PrintDifficulty::Easy, (190, 220), (50, 60)),
+            FilamentMaterial::PLAPlus => (PrintDifficulty::Easy, (200, 230), (50, 60)),
+            FilamentMaterial::PETG => (PrintDifficulty::Medium, (220, 250), (70, 85)),
+            FilamentMaterial::PCTG => (PrintDifficulty::Medium, (230, 260), (70, 90)),
+            FilamentMaterial::TPU => (PrintDifficulty::Hard, (210, 230), (40, 60)),
+            FilamentMaterial::ABS => (PrintDifficulty::Hard, (230, 260), (90, 110)),
+            FilamentMaterial::ASA => (PrintDifficulty::Hard, (240, 260), (90, 110)),
+            FilamentMaterial::Nylon => (PrintDifficulty::Hard, (240, 270), (70, 90)),
+            FilamentMaterial::PC => (PrintDifficulty::Hard, (260, 310), (100, 120)),
+            FilamentMaterial::Unspecified | FilamentMaterial::Other(_) => return None,
+        };
+
+        Some(MaterialProperties {
+            difficulty,
+            nozzle_temp_c,
+            bed_temp_c,
+        })
+    }
+}
+
+/// A recommended printing temperature in °C, either a single value or a range
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemperatureSpec {
+    Exact(u16),
+    Range { min: u16, max: u16 },
+}
+
+impl Display for TemperatureSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemperatureSpec::Exact(t) => write!(f, "{t} °C"),
+            TemperatureSpec::Range { min, max } => write!(f, "{min}–{max} °C"),
+        }
+    }
+}
+
+/// What a listing's weight measures. Listings disagree, which skews $/kg comparisons.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, EnumIter)]
+#[serde(rename_all = "snake_case")]
+pub enum WeightBasis {
+    NetFilament,
+    GrossWithSpool,
+    #[default]
+    Unknown,
+}
+
+impl WeightBasis {
+    pub fn code(&self) -> &'static str {
+        match self {
+            WeightBasis::NetFilament => "net",
+            WeightBasis::GrossWithSpool => "gross",
+            WeightBasis::Unknown => "unknown",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Self {
+        WeightBasis::iter()
+            .find(|b| b.code() == code)
+            .unwrap_or_default()
+    }
+
+    pub fn short_label(&self) -> &'static str {
+        match self {
+            WeightBasis::NetFilament => "net",
+            WeightBasis::GrossWithSpool => "incl. spool",
+            WeightBasis::Unknown => "basis unknown",
+        }
+    }
+}
+
+impl Display for WeightBasis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WeightBasis::NetFilament => write!(f, "Filament only (net)"),
+            WeightBasis::GrossWithSpool => write!(f, "Including spool (gross)"),
+            WeightBasis::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Grams(pub u16);
 
@@ -135,7 +799,7 @@ impl Grams {
 
 impl Display for Grams {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.0 % 1000 == 0 {
+        if self.0.is_multiple_of(1000) {
             write!(f, "{} kg", self.as_kg())
         } else {
             write!(f, "{} g", self.0)
@@ -143,13 +807,15 @@ impl Display for Grams {
     }
 }
 
-/// Filament diameter in hundredths of a millimeter (e.g. 175 = 1.75 mm)
+/// Filament diameter in hundredths of a millimeter (e.g. 175 = 1.75 mm). Zero means the source
+/// didn't say.
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
 #[serde(into = "u16", try_from = "u16")]
 pub enum FilamentDiameter {
     D175,
     D285,
     Other(u16),
+    Unspecified,
 }
 
 impl From<FilamentDiameter> for u16 {
@@ -158,6 +824,7 @@ impl From<FilamentDiameter> for u16 {
             FilamentDiameter::D175 => 175,
             FilamentDiameter::D285 => 285,
             FilamentDiameter::Other(x) => x,
+            FilamentDiameter::Unspecified => 0,
         }
     }
 }
@@ -168,6 +835,7 @@ impl TryFrom<u16> for FilamentDiameter {
         Ok(match v {
             175 => FilamentDiameter::D175,
             285 => FilamentDiameter::D285,
+            0 => FilamentDiameter::Unspecified,
             x => FilamentDiameter::Other(x),
         })
     }
@@ -183,6 +851,7 @@ impl FilamentDiameter {
             FilamentDiameter::D175 => Self::D175_H,
             FilamentDiameter::D285 => Self::D285_H,
             FilamentDiameter::Other(h) => h,
+            FilamentDiameter::Unspecified => 0,
         }
     }
 
@@ -191,6 +860,7 @@ impl FilamentDiameter {
         match h {
             Self::D175_H => FilamentDiameter::D175,
             Self::D285_H => FilamentDiameter::D285,
+            0 => FilamentDiameter::Unspecified,
             x => FilamentDiameter::Other(x),
         }
     }
@@ -213,7 +883,10 @@ impl FilamentDiameter {
 
 impl Display for FilamentDiameter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:.2} mm", self.mm())
+        match self {
+            FilamentDiameter::Unspecified => write!(f, "Unknown"),
+            _ => write!(f, "{:.2} mm", self.mm()),
+        }
     }
 }
 
@@ -244,6 +917,148 @@ impl std::fmt::Display for Retailer {
     }
 }
 
+impl Retailer {
+    /// Guesses the retailer from a product page URL's domain
+    pub fn from_url(url: &str) -> Option<Retailer> {
+        let host = url_host(url)?;
+
+        if host.split('.').any(|part| part == "amazon") {
+            Some(Retailer::Amazon)
+        } else {
+            None
+        }
+    }
+
+    /// Like [`Retailer::from_url`], but names an unrecognized retailer after the URL's domain
+    pub fn guess_from_url(url: &str) -> Option<Retailer> {
+        Retailer::from_url(url).or_else(|| url_host(url).map(Retailer::Other))
+    }
+
+    /// Query parameters this retailer's URLs carry for tracking, beyond the common ones.
+    /// Amazon's affiliate `tag` is deliberately kept.
+    pub fn tracking_params(&self) -> &'static [&'static str] {
+        match self {
+            Retailer::Amazon => &[
+                "ref",
+                "ref_",
+                "pd_rd_*",
+                "pf_rd_*",
+                "qid",
+                "sr",
+                "crid",
+                "sprefix",
+                "keywords",
+                "content-id",
+            ],
+            Retailer::Other(_) => &[],
+        }
+    }
+
+    /// Extracts the retailer's own product ID (e.g. an Amazon ASIN) from a product page URL
+    pub fn product_id_from_url(&self, url: &str) -> Option<String> {
+        match self {
+            Retailer::Amazon => amazon_asin(url),
+            Retailer::Other(_) => None,
+        }
+    }
+}
+
+/// A shipping region a listing can be available in
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, EnumIter)]
+pub enum Region {
+    US,
+    CA,
+    UK,
+    EU,
+    AU,
+}
+
+/// Country codes of the EU member states, for mapping a locale to [`Region::EU`]
+const EU_COUNTRIES: &[&str] = &[
+    "AT", "BE", "BG", "CY", "CZ", "DE", "DK", "EE", "ES", "FI", "FR", "GR", "HR", "HU", "IE", "IT",
+    "LT", "LU", "LV", "MT", "NL", "PL", "PT", "RO", "SE", "SI", "SK",
+];
+
+impl Region {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Region::US => "US",
+            Region::CA => "CA",
+            Region::UK => "UK",
+            Region::EU => "EU",
+            Region::AU => "AU",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Region> {
+        Region::iter().find(|r| r.code().eq_ignore_ascii_case(code))
+    }
+
+    /// The region for a BCP 47 locale such as `en-GB` or `de-DE`, if its country is one we know
+    pub fn from_locale(locale: &str) -> Option<Region> {
+        let country = locale.split(['-', '_']).nth(1)?.to_uppercase();
+
+        match country.as_str() {
+            "US" => Some(Region::US),
+            "CA" => Some(Region::CA),
+            "GB" => Some(Region::UK),
+            "AU" => Some(Region::AU),
+            c if EU_COUNTRIES.contains(&c) => Some(Region::EU),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Region {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Region::US => write!(f, "United States"),
+            Region::CA => write!(f, "Canada"),
+            Region::UK => write!(f, "United Kingdom"),
+            Region::EU => write!(f, "European Union"),
+            Region::AU => write!(f, "Australia"),
+        }
+    }
+}
+
+/// The lowercased host of `url` without any `www.` prefix or port
+fn url_host(url: &str) -> Option<String> {
+    let rest = url
+        .trim()
+        .split_once("://")
+        .map_or(url.trim(), |(_, rest)| rest);
+    let host = rest.split(['/', '?', '#']).next()?;
+    let host = host.rsplit('@').next()?.split(':').next()?.to_lowercase();
+    let host = host.trim_start_matches("www.").to_string();
+
+    if host.is_empty() { None } else { Some(host) }
+}
+
+/// The path segments of `url`, without scheme, host, query, or fragment
+fn url_path_segments(url: &str) -> Vec<&str> {
+    let rest = url
+        .trim()
+        .split_once("://")
+        .map_or(url.trim(), |(_, rest)| rest);
+    let path = rest.split(['?', '#']).next().unwrap_or_default();
+    path.split('/').skip(1).filter(|s| !s.is_empty()).collect()
+}
+
+/// Path segments that precede an ASIN in Amazon URLs, e.g. `/dp/{asin}`, `/gp/product/{asin}`,
+/// `/gp/aw/d/{asin}`, and `/product-reviews/{asin}`
+const AMAZON_ASIN_MARKERS: &[&str] = &["dp", "product", "d", "asin", "product-reviews"];
+
+fn amazon_asin(url: &str) -> Option<String> {
+    let segments = url_path_segments(url);
+
+    segments
+        .windows(2)
+        .filter(|w| AMAZON_ASIN_MARKERS.contains(&w[0].to_ascii_lowercase().as_str()))
+        .map(|w| w[1])
+        .find(|id| id.len() == 10 && id.chars().all(|c| c.is_ascii_alphanumeric()))
+        .map(|id| id.to_ascii_uppercase())
+}
+
 impl From<String> for Retailer {
     fn from(s: String) -> Self {
         Retailer::from_str(&s).unwrap()
@@ -387,13 +1202,14 @@ impl From<FilamentColor> for String {
 impl FilamentColor {
     pub fn hex(&self) -> &'static str {
         match self {
-            Self::Black => "#FFFFFF",
+            Self::Black => "#000000",
             Self::White => "#FFFFFF",
             Self::Gray => "#808080",
             Self::Silver => "#C0C0C0",
             Self::Brown => "#8B4513",
             Self::Beige => "#F5F5DC",
-            Self::Transparent => "#FFFFFF",
+            // A placeholder; swatches draw it with a dashed outline
+            Self::Transparent => "#CCCCCC",
             Self::Red => "#FF0000",
             Self::Blue => "#0000FF",
             Self::Green => "#008000",
@@ -413,3 +1229,411 @@ impl FilamentColor {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A product that passes validation
+    fn product() -> Product {
+        Product {
+            uuid: String::new(),
+            name: "Polymaker PLA Black 1kg".to_string(),
+            price: Cents(1999),
+            list_price: None,
+            price_per_kg: Cents(1999),
+            currency: Currency::Usd,
+            url: "https://www.amazon.com/dp/B07PGY2JP1".to_string(),
+            material: FilamentMaterial::PLA,
+            diameter: FilamentDiameter::D175,
+            weight: Grams(1000),
+            retailer: Retailer::Amazon,
+            retailer_product_id: "B07PGY2JP1".to_string(),
+            color: FilamentColor::Black,
+            weight_basis: WeightBasis::NetFilament,
+            empty_spool_weight: None,
+            in_stock: None,
+            ships_to: vec![],
+            tags: vec![],
+            nozzle_temp: None,
+            bed_temp: None,
+            description: None,
+            updated_at: None,
+            updated_by: None,
+            price_updated_at: None,
+        }
+    }
+
+    /// The fields `product` fails validation on
+    fn invalid_fields(product: &Product) -> Vec<ProductField> {
+        match product.validate() {
+            Ok(()) => vec![],
+            Err(errors) => errors.into_iter().map(|e| e.field).collect(),
+        }
+    }
+
+    #[test]
+    fn valid_product_passes() {
+        assert_eq!(product().validate(), Ok(()));
+    }
+
+    #[test]
+    fn blank_name_is_invalid() {
+        let product = Product {
+            name: "   ".to_string(),
+            ..product()
+        };
+
+        assert_eq!(invalid_fields(&product), vec![ProductField::Name]);
+    }
+
+    #[test]
+    fn zero_price_is_invalid() {
+        let product = Product {
+            price: Cents(0),
+            ..product()
+        };
+
+        assert_eq!(invalid_fields(&product), vec![ProductField::Price]);
+    }
+
+    #[test]
+    fn zero_weight_is_invalid() {
+        let product = Product {
+            weight: Grams(0),
+            ..product()
+        };
+
+        assert_eq!(invalid_fields(&product), vec![ProductField::Weight]);
+    }
+
+    #[test]
+    fn diameter_bounds() {
+        for (diameter, valid) in [
+            (FilamentDiameter::Other(99), false),
+            (FilamentDiameter::Other(100), true),
+            (FilamentDiameter::D285, true),
+            (FilamentDiameter::Other(400), true),
+            (FilamentDiameter::Other(401), false),
+            (FilamentDiameter::Unspecified, true),
+        ] {
+            let product = Product {
+                diameter,
+                ..product()
+            };
+            let expected = if valid {
+                vec![]
+            } else {
+                vec![ProductField::Diameter]
+            };
+
+            assert_eq!(invalid_fields(&product), expected, "{diameter:?}");
+        }
+    }
+
+    #[test]
+    fn malformed_url_is_invalid() {
+        for url in [
+            "",
+            "amazon.com/dp/B07PGY2JP1",
+            "ftp://example.com/x",
+            "https://localhost/x",
+        ] {
+            let product = Product {
+                url: url.to_string(),
+                ..product()
+            };
+
+            assert_eq!(invalid_fields(&product), vec![ProductField::Url], "{url}");
+        }
+    }
+
+    #[test]
+    fn overlong_other_names_are_invalid() {
+        let long = "x".repeat(MAX_OTHER_LEN + 1);
+        let product = Product {
+            material: FilamentMaterial::Other(long.clone()),
+            color: FilamentColor::Other(long),
+            ..product()
+        };
+
+        assert_eq!(
+            invalid_fields(&product),
+            vec![ProductField::Material, ProductField::Color]
+        );
+    }
+
+    #[test]
+    fn overlong_description_is_invalid() {
+        let product = Product {
+            description: Some("x".repeat(MAX_DESCRIPTION_LEN + 1)),
+            ..product()
+        };
+
+        assert_eq!(invalid_fields(&product), vec![ProductField::Description]);
+    }
+
+    #[test]
+    fn every_problem_is_reported() {
+        let product = Product {
+            name: String::new(),
+            price: Cents(0),
+            weight: Grams(0),
+            ..product()
+        };
+
+        assert_eq!(
+            invalid_fields(&product),
+            vec![
+                ProductField::Name,
+                ProductField::Price,
+                ProductField::Weight
+            ]
+        );
+    }
+
+    #[test]
+    fn asin_from_amazon_url_shapes() {
+        for url in [
+            "https://www.amazon.com/dp/B07PGY2JP1",
+            "https://www.amazon.com/Polymaker-PLA-Filament/dp/B07PGY2JP1/ref=sr_1_3",
+            "https://amazon.co.uk/gp/product/B07PGY2JP1",
+            "https://www.amazon.com/gp/aw/d/B07PGY2JP1",
+            "https://www.amazon.com/product-reviews/B07PGY2JP1",
+            "https://www.amazon.de/dp/b07pgy2jp1?th=1&psc=1#reviews",
+        ] {
+            assert_eq!(
+                Retailer::Amazon.product_id_from_url(url),
+                Some("B07PGY2JP1".to_string()),
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn no_asin_in_short_or_search_links() {
+        for url in [
+            "https://amzn.to/3xYzAbC",
+            "https://a.co/d/abc1234",
+            "https://www.amazon.com/s?k=pla+filament",
+            "https://www.amazon.com/dp/TOO-SHORT",
+        ] {
+            assert_eq!(Retailer::Amazon.product_id_from_url(url), None, "{url}");
+        }
+    }
+
+    #[test]
+    fn other_retailers_leave_product_id_blank() {
+        let retailer = Retailer::Other("prusa3d.com".to_string());
+
+        assert_eq!(
+            retailer.product_id_from_url("https://www.prusa3d.com/product/prusament-pla/"),
+            None
+        );
+    }
+
+    #[test]
+    fn slugify_lowercases_and_hyphenates() {
+        assert_eq!(
+            slugify("Polymaker PLA Black 1kg"),
+            "polymaker-pla-black-1kg"
+        );
+    }
+
+    #[test]
+    fn slugify_drops_punctuation() {
+        assert_eq!(slugify("eSUN PLA+ (1.75mm), 1kg!"), "esun-pla-1-75mm-1kg");
+    }
+
+    #[test]
+    fn slugify_collapses_repeated_separators() {
+        assert_eq!(slugify("  PETG -- / -- Clear  "), "petg-clear");
+    }
+
+    #[test]
+    fn slugify_drops_non_ascii() {
+        assert_eq!(
+            slugify("Prusament PETG Jet Black – 1 kg"),
+            "prusament-petg-jet-black-1-kg"
+        );
+        assert_eq!(slugify("Filament für Drucker"), "filament-f-r-drucker");
+    }
+
+    #[test]
+    fn slugify_can_be_empty() {
+        for input in ["", "   ", "!!!", "日本語"] {
+            assert_eq!(slugify(input), "", "{input:?}");
+        }
+    }
+
+    #[test]
+    fn canonical_path_without_slug_is_bare_uuid() {
+        let product = Product {
+            uuid: "u-1".to_string(),
+            name: "—".to_string(),
+            ..product()
+        };
+
+        assert_eq!(product.canonical_path(), "/product/u-1");
+    }
+
+    #[test]
+    fn normalize_name_trims_and_collapses_whitespace() {
+        assert_eq!(
+            normalize_name("  Polymaker   PLA \t Black\n"),
+            "Polymaker PLA Black"
+        );
+        assert_eq!(normalize_name("   "), "");
+    }
+
+    #[test]
+    fn normalize_url_strips_common_tracking() {
+        let other = Retailer::Other("example.com".to_string());
+
+        assert_eq!(
+            normalize_url(
+                " https://example.com/p?id=7&utm_source=x&utm_campaign=y&fbclid=z ",
+                &other
+            ),
+            "https://example.com/p?id=7"
+        );
+        assert_eq!(
+            normalize_url("https://example.com/p?gclid=1", &other),
+            "https://example.com/p"
+        );
+    }
+
+    #[test]
+    fn normalize_url_strips_retailer_tracking() {
+        assert_eq!(
+            normalize_url(
+                "https://www.amazon.com/dp/B07PGY2JP1?ref_=abc&pd_rd_w=1&th=1&tag=seek-20",
+                &Retailer::Amazon
+            ),
+            "https://www.amazon.com/dp/B07PGY2JP1?th=1&tag=seek-20"
+        );
+    }
+
+    #[test]
+    fn normalize_url_keeps_other_retailers_params() {
+        let url = "https://example.com/p?ref=home&qid=2";
+
+        assert_eq!(
+            normalize_url(url, &Retailer::Other("example.com".to_string())),
+            url
+        );
+    }
+
+    #[test]
+    fn normalize_url_keeps_fragment() {
+        assert_eq!(
+            normalize_url(
+                "https://example.com/p?utm_medium=email#specs",
+                &Retailer::Other(String::new())
+            ),
+            "https://example.com/p#specs"
+        );
+    }
+
+    #[test]
+    fn compact_format_drops_cents_on_whole_amounts() {
+        assert_eq!(Cents(1900).format_compact(Currency::Usd), "$19");
+        assert_eq!(Cents(0).format_compact(Currency::Usd), "$0");
+        assert_eq!(Cents(120_000).format_compact(Currency::Eur), "€1200");
+    }
+
+    #[test]
+    fn compact_format_keeps_fractional_cents() {
+        assert_eq!(Cents(1999).format_compact(Currency::Usd), "$19.99");
+        assert_eq!(Cents(1905).format_compact(Currency::Gbp), "£19.05");
+        assert_eq!(Cents(1910).format_compact(Currency::Usd), "$19.10");
+    }
+
+    #[test]
+    fn display_keeps_both_decimals() {
+        assert_eq!(Cents(1900).to_string(), "$19.00");
+    }
+
+    #[test]
+    fn dollars_str_parses_plain_amounts() {
+        assert_eq!(Cents::from_dollars_str("19.99"), Some(Cents(1999)));
+        assert_eq!(Cents::from_dollars_str(" 19 "), Some(Cents(1900)));
+        assert_eq!(Cents::from_dollars_str("0.5"), Some(Cents(50)));
+    }
+
+    #[test]
+    fn dollars_str_strips_currency_symbol() {
+        assert_eq!(Cents::from_dollars_str("$19.99"), Some(Cents(1999)));
+        assert_eq!(Cents::from_dollars_str("$ 19.99"), Some(Cents(1999)));
+        assert_eq!(Cents::from_dollars_str("€12"), Some(Cents(1200)));
+        assert_eq!(Cents::from_dollars_str("£12.50"), Some(Cents(1250)));
+        assert_eq!(Cents::from_dollars_str("€19,99"), Some(Cents(1999)));
+    }
+
+    #[test]
+    fn dollars_str_rounds_extra_decimals() {
+        assert_eq!(Cents::from_dollars_str("$19.999"), Some(Cents(2000)));
+        assert_eq!(Cents::from_dollars_str("19.994"), Some(Cents(1999)));
+    }
+
+    #[test]
+    fn dollars_str_reads_decimal_and_grouping_commas() {
+        assert_eq!(Cents::from_dollars_str("19,99"), Some(Cents(1999)));
+        assert_eq!(Cents::from_dollars_str("19,9"), Some(Cents(1990)));
+        assert_eq!(Cents::from_dollars_str("$1,299"), Some(Cents(129_900)));
+        assert_eq!(Cents::from_dollars_str("1,299.50"), Some(Cents(129_950)));
+    }
+
+    #[test]
+    fn dollars_str_rejects_negative_empty_and_garbage() {
+        for input in ["-5", "$-5", "", "   ", "$", "abc", "1.2.3", "1e3"] {
+            assert_eq!(Cents::from_dollars_str(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn dollars_str_rejects_amounts_too_large() {
+        assert_eq!(Cents::from_dollars_str("99999999999"), None);
+    }
+
+    #[test]
+    fn dollars_str_round_trips() {
+        for cents in [0, 5, 1999, 129_900] {
+            assert_eq!(
+                Cents::from_dollars_str(&Cents(cents).to_dollars_str()),
+                Some(Cents(cents))
+            );
+        }
+    }
+
+    #[test]
+    fn plain_decimal_has_no_symbol_or_grouping() {
+        assert_eq!(Cents(129_900).to_plain_decimal(), "1299.00");
+        assert_eq!(Cents(5).to_plain_decimal(), "0.05");
+        assert_eq!(Cents(u32::MAX).to_plain_decimal(), "42949672.95");
+    }
+
+    #[test]
+    fn from_dollars_rounds_normal_amounts() {
+        assert_eq!(Cents::from_dollars(19.99), Cents(1999));
+        assert_eq!(Cents::from_dollars(0.01), Cents(1));
+        assert_eq!(Cents::from_dollars(0.0), Cents(0));
+    }
+
+    #[test]
+    fn from_dollars_clamps_negative_to_zero() {
+        assert_eq!(Cents::from_dollars(-1.0), Cents(0));
+        assert_eq!(Cents::from_dollars(f32::NEG_INFINITY), Cents(0));
+    }
+
+    #[test]
+    fn from_dollars_treats_nan_as_zero() {
+        assert_eq!(Cents::from_dollars(f32::NAN), Cents(0));
+    }
+
+    #[test]
+    fn from_dollars_saturates_huge_amounts() {
+        assert_eq!(Cents::from_dollars(f32::MAX), Cents(u32::MAX));
+        assert_eq!(Cents::from_dollars(f32::INFINITY), Cents(0));
+    }
+}
@@ -0,0 +1,153 @@
+use gloo_net::http::Method;
+use leptos::{prelude::*, reactive::spawn_local};
+use leptos_router::{NavigateOptions, hooks::use_params_map};
+
+use crate::{
+    clipboard,
+    color_swatch::ColorChip,
+    preferences::use_preferences,
+    product::{Product, Retailer},
+    product_search::FreshnessBadge,
+    request::{Auth, request_json},
+    resource::{LoadState, use_resource},
+    restock_alert::RestockAlert,
+    session::use_is_admin,
+};
+
+/// A single product, at `/product/:uuid` or `/product/:uuid/:slug`. Any other slug, or none,
+/// is replaced in the address bar by the product's canonical one.
+#[component]
+pub fn ProductPage() -> impl IntoView {
+    let params = use_params_map();
+    let uuid = move || params.read().get("uuid");
+    let navigate = leptos_router::hooks::use_navigate();
+
+    let product = use_resource(uuid, |uuid| async move {
+        let path = format!("products/{uuid}");
+        request_json::<(), Product>(&path, Auth::Unauthorized, Method::GET, None).await
+    });
+
+    Effect::new(move |_| {
+        if let LoadState::Loaded(p) = product.get() {
+            let slug = params.read_untracked().get("slug").unwrap_or_default();
+
+            if slug != p.slug() {
+                navigate(
+                    &p.canonical_path(),
+                    NavigateOptions {
+                        replace: true,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    });
+
+    view! {
+        <div class="container">
+            <p><a href="/">"← Back to search"</a></p>
+            <div class="card">
+                {move || match product.get() {
+                    LoadState::Idle | LoadState::Loading => {
+                        view! { <div class="loading">"Loading…"</div> }.into_any()
+                    }
+                    LoadState::Failed(e) if e.status == 404 => {
+                        view! { <div class="empty">"This product doesn't exist or was removed."</div> }.into_any()
+                    }
+                    LoadState::Failed(e) => {
+                        view! { <p class="error">{format!("Failed to load product: ({}) {}", e.status, e.message)}</p> }
+                            .into_any()
+                    }
+                    LoadState::Loaded(p) => view! { <ProductDetails product=p /> }.into_any(),
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ProductDetails(product: Product) -> impl IntoView {
+    let prefs = use_preferences();
+    let is_admin = use_is_admin();
+    let target = move || {
+        if prefs.with(|p| p.open_links_in_new_tab) {
+            "_blank"
+        } else {
+            "_self"
+        }
+    };
+
+    let price_per_kg = product
+        .displayable_price_per_kg()
+        .map(|ppk| format!("{}/kg", ppk.format_in(product.currency)))
+        .unwrap_or_else(|| "—".to_string());
+
+    let temps = [("Nozzle", product.nozzle_temp), ("Bed", product.bed_temp)]
+        .into_iter()
+        .filter_map(|(label, t)| t.map(|t| format!("{label} {t}")))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    view! {
+        <h1>{product.name.clone()}</h1>
+        {product.description.clone().map(|d| view! { <p class="product-description">{d}</p> })}
+        <dl class="product-details">
+            <dt>"Price"</dt>
+            <dd>{product.price.format_in(product.currency)} " " <FreshnessBadge product=product.clone() /></dd>
+            <dt>"Per kg"</dt>
+            <dd>{price_per_kg}</dd>
+            <dt>"Material"</dt>
+            <dd>{product.material.to_string()}</dd>
+            <dt>"Color"</dt>
+            <dd>
+                <ColorChip color=product.color.clone() />
+                {product.color.to_string()}
+            </dd>
+            <dt>"Diameter"</dt>
+            <dd>{product.diameter.to_string()}</dd>
+            <dt>"Weight"</dt>
+            <dd>{product.weight_label()}</dd>
+            <dt>"Retailer"</dt>
+            <dd>{product.retailer.to_string()}</dd>
+            {(!temps.is_empty()).then(|| view! { <dt>"Temperatures"</dt><dd>{temps}</dd> })}
+        </dl>
+        {(product.in_stock == Some(false))
+            .then(|| view! { <RestockAlert product_uuid=product.uuid.clone() /> })}
+        <a href=product.url.clone() target=target>"View at retailer"</a>
+        {(product.retailer == Retailer::Amazon).then_some(" (#ad)")}
+        // The raw API payload is offered in development builds, and to admins
+        {move || {
+            (cfg!(debug_assertions) || is_admin.get())
+                .then(|| view! { <RawJson product=product.clone() /> })
+        }}
+    }
+}
+
+/// The product exactly as the API serializes it, for debugging
+#[component]
+fn RawJson(product: Product) -> impl IntoView {
+    let json = serde_json::to_string_pretty(&product).unwrap_or_else(|e| e.to_string());
+    let json = StoredValue::new(json);
+    let (copied, set_copied) = signal::<Option<bool>>(None);
+
+    let on_copy = move |_| {
+        spawn_local(async move {
+            let copied = clipboard::copy_to_clipboard(&json.get_value()).await;
+            set_copied.set(Some(copied));
+        });
+    };
+
+    view! {
+        <details class="raw-json">
+            <summary>"Raw JSON"</summary>
+            <button class="link-button" on:click=on_copy>
+                {move || match copied.get() {
+                    Some(true) => "Copied",
+                    Some(false) => "Copy failed; select the text below",
+                    None => "Copy",
+                }}
+            </button>
+            <pre class="selectable">{json.get_value()}</pre>
+        </details>
+    }
+}
@@ -1,33 +1,225 @@
-use std::{fmt::Display, str::FromStr};
+use std::{collections::HashSet, fmt::Display, str::FromStr, time::Duration};
 
 use gloo_net::http::Method;
-use leptos::{prelude::*, reactive::spawn_local};
+use leptos::{ev, html, prelude::*, reactive::spawn_local};
 use serde::{Deserialize, Serialize};
+use strum::IntoEnumIterator;
 
 use crate::{
+    clipboard,
+    color_swatch::ColorChip,
+    drawer::Drawer,
+    favorites::FavoriteButton,
+    materials_legend::MaterialsLegend,
+    modal::Modal,
+    preferences::{Density, Preferences, TableColumn, use_preferences},
     product::{
-        Cents, FilamentColor, FilamentDiameter, FilamentMaterial, Grams, KNOWN_COLORS,
-        KNOWN_MATERIALS, Product, Retailer,
+        Cents, Currency, FilamentColor, FilamentDiameter, FilamentMaterial, Grams, KNOWN_COLORS,
+        KNOWN_MATERIALS, MAX_OTHER_LEN, Product, Region, Retailer, clamp_other, humanize_age,
+        normalize_tag,
     },
-    request::{Auth, request_json},
+    request::{Auth, ErrorResponse, request_json},
+    saved_searches::SaveSearchButton,
+    session::use_is_admin,
+    shortcuts,
+    tag_input::TagInput,
+    taxonomy::use_taxonomy,
+    toast::use_toasts,
+    viewport::{NARROW_MAX_WIDTH, use_viewport_width},
 };
 
 const MAX_PRICE_CAP: u32 = 100;
-const MAX_PAGE_SIZE: u32 = 50;
+/// Least distance the price slider keeps between its two ends, in whole units
+const PRICE_GAP: u32 = 1;
+
+/// Fewer priced products than this and the $/kg stats are hidden as not meaningful
+const MIN_STATS_SAMPLES: u64 = 5;
+/// How long a copy confirmation stays before the button resets, in milliseconds
+const COPY_STATUS_MS: u64 = 2000;
+
+/// Choices, in minutes, for how often a watched search is re-run in the background
+const WATCH_INTERVALS_MINS: &[u32] = &[1, 5, 15, 60];
+
+/// How long the price range must stay still before it is applied
+const PRICE_DEBOUNCE_MS: u64 = 400;
+/// Changes to the URL within this long of each other become one history entry
+const URL_DEBOUNCE_MS: u64 = 250;
+
+/// A $/kg more than this many interquartile ranges below the median is flagged as unusually low
+const OUTLIER_IQR_K: f32 = 1.5;
+/// Fewer priced products than this on a page and no outliers are flagged
+const MIN_OUTLIER_SAMPLES: usize = 8;
+/// Most columns a search can be sorted by at once
+const MAX_SORT_KEYS: usize = 3;
 
 #[derive(Clone, Debug, PartialEq)]
-enum MaterialFilter {
+pub enum MaterialFilter {
     Any,
-    Material(FilamentMaterial),
+    /// Any of these materials; never empty, since choosing none is `Any`
+    Materials(Vec<FilamentMaterial>),
     Other(String),
     Unspecified,
 }
 
-#[derive(Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum SortBy {
     Price,
     PricePerKg,
+    UpdatedAt,
+    Material,
+    Name,
+    Weight,
+}
+
+impl SortBy {
+    fn name(&self) -> &'static str {
+        match self {
+            SortBy::Price => "price",
+            SortBy::PricePerKg => "price_per_kg",
+            SortBy::UpdatedAt => "updated_at",
+            SortBy::Material => "material",
+            SortBy::Name => "name",
+            SortBy::Weight => "weight",
+        }
+    }
+
+    /// The column's name as read out by screen readers
+    fn spoken_name(&self) -> &'static str {
+        match self {
+            SortBy::Price => "price",
+            SortBy::PricePerKg => "price per kilogram",
+            SortBy::UpdatedAt => "last updated",
+            SortBy::Material => "material",
+            SortBy::Name => "name",
+            SortBy::Weight => "weight",
+        }
+    }
+}
+
+/// The `aria-sort` value for `by`'s column header. Only the primary key is reported, since
+/// ARIA allows one sorted column at a time.
+fn aria_sort(keys: &[SortKey], by: SortBy) -> &'static str {
+    match keys.first() {
+        Some(k) if k.by == by => match k.direction {
+            SortDirection::Asc => "ascending",
+            SortDirection::Desc => "descending",
+        },
+        _ => "none",
+    }
+}
+
+impl FromStr for SortBy {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            SortBy::Price,
+            SortBy::PricePerKg,
+            SortBy::UpdatedAt,
+            SortBy::Material,
+            SortBy::Name,
+            SortBy::Weight,
+        ]
+        .into_iter()
+        .find(|b| b.name() == s)
+        .ok_or(())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// One level of an ordered sort, e.g. "material ascending"
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct SortKey {
+    pub by: SortBy,
+    pub direction: SortDirection,
+}
+
+impl SortKey {
+    pub fn asc(by: SortBy) -> Self {
+        Self {
+            by,
+            direction: SortDirection::Asc,
+        }
+    }
+}
+
+/// Form in the multi-key `sort` URL parameter: the field name, prefixed with `-` when descending
+impl Display for SortKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.direction {
+            SortDirection::Asc => write!(f, "{}", self.by.name()),
+            SortDirection::Desc => write!(f, "-{}", self.by.name()),
+        }
+    }
+}
+
+impl FromStr for SortKey {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('-') {
+            Some(by) => Ok(SortKey {
+                by: by.parse()?,
+                direction: SortDirection::Desc,
+            }),
+            None => Ok(SortKey::asc(s.parse()?)),
+        }
+    }
+}
+
+/// Updates the sort for a header click: a plain click sorts by that column alone (flipping its
+/// direction if it already was), an additive (shift) click adds it as the next tie-breaker
+fn apply_sort_click(keys: &mut Vec<SortKey>, by: SortBy, additive: bool) {
+    if additive {
+        if let Some(key) = keys.iter_mut().find(|k| k.by == by) {
+            key.direction = flip(key.direction);
+        } else if keys.len() < MAX_SORT_KEYS {
+            keys.push(SortKey::asc(by));
+        }
+        return;
+    }
+
+    match keys.as_slice() {
+        [only] if only.by == by => keys[0].direction = flip(only.direction),
+        _ => *keys = vec![SortKey::asc(by)],
+    }
+
+    fn flip(d: SortDirection) -> SortDirection {
+        match d {
+            SortDirection::Asc => SortDirection::Desc,
+            SortDirection::Desc => SortDirection::Asc,
+        }
+    }
+}
+
+/// Orders a page of results by `keys`, falling back to UUID so equal rows never reshuffle
+fn sort_products(products: &mut [Product], keys: &[SortKey]) {
+    products.sort_by(|a, b| {
+        keys.iter()
+            .map(|k| {
+                let ordering = match k.by {
+                    SortBy::Price => a.price.cmp(&b.price),
+                    SortBy::PricePerKg => a.price_per_kg.cmp(&b.price_per_kg),
+                    SortBy::UpdatedAt => a.updated_at.cmp(&b.updated_at),
+                    SortBy::Material => a.material.to_string().cmp(&b.material.to_string()),
+                    SortBy::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+                    SortBy::Weight => a.weight.0.cmp(&b.weight.0),
+                };
+                match k.direction {
+                    SortDirection::Asc => ordering,
+                    SortDirection::Desc => ordering.reverse(),
+                }
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| a.uuid.cmp(&b.uuid))
+    });
 }
 
 impl FromStr for MaterialFilter {
@@ -40,12 +232,28 @@ impl FromStr for MaterialFilter {
             Ok(MaterialFilter::Unspecified)
         } else if s == "Other" {
             Ok(MaterialFilter::Other(String::new()))
+        } else if let Some(other) = s.strip_prefix("Other: ") {
+            Ok(MaterialFilter::Other(clamp_other(other)))
         } else {
-            let chosen = KNOWN_MATERIALS.iter().find(|m| m.to_string() == s).cloned();
-            if let Some(m) = chosen {
-                Ok(MaterialFilter::Material(m))
-            } else {
+            let mut chosen: Vec<FilamentMaterial> = vec![];
+
+            for name in s.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+                // Names outside the built-in list may be materials the server's taxonomy adds
+                let m = KNOWN_MATERIALS
+                    .iter()
+                    .find(|m| m.to_string() == name)
+                    .cloned()
+                    .unwrap_or_else(|| FilamentMaterial::Other(clamp_other(name)));
+
+                if !chosen.contains(&m) {
+                    chosen.push(m);
+                }
+            }
+
+            if chosen.is_empty() {
                 Err(())
+            } else {
+                Ok(MaterialFilter::Materials(chosen))
             }
         }
     }
@@ -55,7 +263,10 @@ impl Display for MaterialFilter {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             MaterialFilter::Any => write!(f, "Any"),
-            MaterialFilter::Material(m) => write!(f, "{}", m),
+            MaterialFilter::Materials(ms) => {
+                let names: Vec<String> = ms.iter().map(|m| m.to_string()).collect();
+                write!(f, "{}", names.join(","))
+            }
             MaterialFilter::Other(s) => write!(f, "Other: {}", s),
             MaterialFilter::Unspecified => write!(f, "Unspecified"),
         }
@@ -63,7 +274,7 @@ impl Display for MaterialFilter {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum ColorFilter {
+pub enum ColorFilter {
     Any,
     Material(FilamentColor),
     Other(String),
@@ -80,12 +291,16 @@ impl FromStr for ColorFilter {
             Ok(ColorFilter::Unspecified)
         } else if s == "Other" {
             Ok(ColorFilter::Other(String::new()))
+        } else if let Some(other) = s.strip_prefix("Other: ") {
+            Ok(ColorFilter::Other(clamp_other(other)))
         } else {
             let chosen = KNOWN_COLORS.iter().find(|c| c.to_string() == s).cloned();
-            if let Some(c) = chosen {
-                Ok(ColorFilter::Material(c))
-            } else {
-                Err(())
+            match chosen {
+                Some(c) => Ok(ColorFilter::Material(c)),
+                None if !s.trim().is_empty() => {
+                    Ok(ColorFilter::Material(FilamentColor::Other(clamp_other(s))))
+                }
+                None => Err(()),
             }
         }
     }
@@ -103,11 +318,12 @@ impl Display for ColorFilter {
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum DiameterFilter {
+pub enum DiameterFilter {
     Any,
     D175,
     D285,
     Other(String),
+    Unspecified,
 }
 
 impl Display for DiameterFilter {
@@ -117,6 +333,7 @@ impl Display for DiameterFilter {
             DiameterFilter::D175 => write!(f, "1.75"),
             DiameterFilter::D285 => write!(f, "2.85"),
             DiameterFilter::Other(s) => write!(f, "Other: {}", s),
+            DiameterFilter::Unspecified => write!(f, "Unspecified"),
         }
     }
 }
@@ -129,14 +346,18 @@ impl FromStr for DiameterFilter {
             "Any" => Ok(DiameterFilter::Any),
             "1.75" => Ok(DiameterFilter::D175),
             "2.85" => Ok(DiameterFilter::D285),
+            "Unspecified" => Ok(DiameterFilter::Unspecified),
             "Other" => Ok(DiameterFilter::Other(String::new())),
-            _ => Err(()),
+            other => other
+                .strip_prefix("Other: ")
+                .map(|v| DiameterFilter::Other(v.to_string()))
+                .ok_or(()),
         }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-enum WeightFilter {
+pub enum WeightFilter {
     Any,
     G500,
     G750,
@@ -169,44 +390,664 @@ impl FromStr for WeightFilter {
             "1000" => Ok(WeightFilter::G1000),
             "2000" => Ok(WeightFilter::G2000),
             "Other" => Ok(WeightFilter::Other(String::new())),
-            _ => Err(()),
+            other => other
+                .strip_prefix("Other: ")
+                .map(|v| WeightFilter::Other(v.to_string()))
+                .ok_or(()),
         }
     }
 }
 
-#[derive(Serialize)]
+impl MaterialFilter {
+    /// The material for the request's single-material filter; `None` when several are chosen,
+    /// which go in [`MaterialFilter::to_materials`] instead
+    pub fn to_material(&self) -> Option<FilamentMaterial> {
+        match self {
+            MaterialFilter::Any => None,
+            MaterialFilter::Materials(ms) => match ms.as_slice() {
+                [m] => Some(m.clone()),
+                _ => None,
+            },
+            MaterialFilter::Other(s) => {
+                if s.trim().is_empty() {
+                    None
+                } else {
+                    Some(FilamentMaterial::Other(clamp_other(s)))
+                }
+            }
+            MaterialFilter::Unspecified => Some(FilamentMaterial::Unspecified),
+        }
+    }
+
+    /// The chosen materials when there are two or more
+    pub fn to_materials(&self) -> Option<Vec<FilamentMaterial>> {
+        match self {
+            MaterialFilter::Materials(ms) if ms.len() > 1 => Some(ms.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl ColorFilter {
+    pub fn to_color(&self) -> Option<FilamentColor> {
+        match self {
+            ColorFilter::Any => None,
+            ColorFilter::Material(c) => Some(c.clone()),
+            ColorFilter::Other(s) => {
+                if s.trim().is_empty() {
+                    None
+                } else {
+                    Some(FilamentColor::Other(clamp_other(s)))
+                }
+            }
+            ColorFilter::Unspecified => Some(FilamentColor::Unspecified),
+        }
+    }
+}
+
+impl DiameterFilter {
+    pub fn to_diameter(&self) -> Option<FilamentDiameter> {
+        match self {
+            DiameterFilter::Any => None,
+            DiameterFilter::D175 => Some(FilamentDiameter::D175),
+            DiameterFilter::D285 => Some(FilamentDiameter::D285),
+            DiameterFilter::Other(s) => {
+                if s.trim().is_empty() {
+                    None
+                } else {
+                    Some(FilamentDiameter::from_mm_string(s))
+                }
+            }
+            DiameterFilter::Unspecified => Some(FilamentDiameter::Unspecified),
+        }
+    }
+}
+
+impl WeightFilter {
+    pub fn to_weight(&self) -> Option<Grams> {
+        match self {
+            WeightFilter::Any => None,
+            WeightFilter::G500 => Some(Grams(500)),
+            WeightFilter::G750 => Some(Grams(750)),
+            WeightFilter::G1000 => Some(Grams(1000)),
+            WeightFilter::G2000 => Some(Grams(2000)),
+            WeightFilter::Other(s) => {
+                if s.trim().is_empty() {
+                    None
+                } else {
+                    Some(Grams::from_kg_string(s))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
 pub struct ProductSearchRequest {
-    name: Option<String>,
-    min_price: Option<Cents>,
-    max_price: Option<Cents>,
-    material: Option<FilamentMaterial>,
-    diameter: Option<FilamentDiameter>,
-    weight: Option<Grams>,
-    color: Option<FilamentColor>,
-    page: u32,
-    per_page: u32,
-    sort_by: Option<SortBy>,
+    pub name: Option<String>,
+    pub min_price: Option<Cents>,
+    pub max_price: Option<Cents>,
+    /// What `min_price` and `max_price` are in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    pub material: Option<FilamentMaterial>,
+    /// Matches any of these; sent instead of `material` when more than one is chosen
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub materials: Option<Vec<FilamentMaterial>>,
+    pub diameter: Option<FilamentDiameter>,
+    pub weight: Option<Grams>,
+    pub color: Option<FilamentColor>,
+    pub page: u32,
+    pub per_page: u32,
+    /// The primary sort key, for backends that only understand one
+    pub sort_by: Option<SortBy>,
+    /// Every sort key in priority order
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sort: Vec<SortKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_sale: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_match: Option<TagMatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<Region>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region_mode: Option<RegionMode>,
+    /// Whether listings that don't say where they ship are excluded by `region_mode`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hide_unknown_region: Option<bool>,
+    /// Drop products whose material is `Unspecified`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_unspecified_material: Option<bool>,
+    /// Drop products of these materials
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_materials: Option<Vec<FilamentMaterial>>,
+    /// Drop products whose color is `Unspecified`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exclude_unspecified_color: Option<bool>,
+    /// Ask for price and $/kg statistics over every matching product, not just this page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregates: Option<bool>,
+}
+
+impl ProductSearchRequest {
+    /// The fields that narrow the results, by their serialized names. The default price cap is
+    /// sent with every search, so it doesn't count.
+    fn requested_filters(&self) -> Vec<&'static str> {
+        [
+            ("name", self.name.is_some()),
+            ("min_price", self.min_price.is_some_and(|p| p.0 > 0)),
+            (
+                "max_price",
+                self.max_price.is_some()
+                    && self.max_price != Cents::from_whole_dollars(MAX_PRICE_CAP),
+            ),
+            ("material", self.material.is_some()),
+            ("materials", self.materials.is_some()),
+            ("diameter", self.diameter.is_some()),
+            ("weight", self.weight.is_some()),
+            ("color", self.color.is_some()),
+            ("on_sale", self.on_sale.is_some()),
+            ("tags", self.tags.is_some()),
+            ("region", self.region.is_some()),
+            (
+                "exclude_unspecified_material",
+                self.exclude_unspecified_material.is_some(),
+            ),
+            ("exclude_materials", self.exclude_materials.is_some()),
+            (
+                "exclude_unspecified_color",
+                self.exclude_unspecified_color.is_some(),
+            ),
+        ]
+        .into_iter()
+        .filter_map(|(name, requested)| requested.then_some(name))
+        .collect()
+    }
+
+    /// Filters this request asked for that `response` reports it didn't apply.
+    /// Empty when the backend doesn't say which filters it applied.
+    pub fn unapplied_filters(&self, response: &ProductSearchResponse) -> Vec<&'static str> {
+        let Some(applied) = &response.applied_filters else {
+            return vec![];
+        };
+
+        self.requested_filters()
+            .into_iter()
+            .filter(|f| !applied.iter().any(|a| a == f))
+            .collect()
+    }
+
+    /// Narrows the request to the user's region, when their preferences ask for it
+    pub fn with_region_preference(mut self, prefs: &Preferences) -> Self {
+        let region = prefs
+            .region
+            .filter(|_| prefs.region_mode != RegionMode::Anywhere);
+
+        self.region = region;
+        self.region_mode = region.map(|_| prefs.region_mode);
+        self.hide_unknown_region = region.map(|_| prefs.hide_unknown_region);
+        self
+    }
+
+    /// Asks for full-result statistics, when the user has the stats strip shown
+    pub fn with_stats_preference(mut self, prefs: &Preferences) -> Self {
+        self.aggregates = prefs.show_stats.then_some(true);
+        self
+    }
+
+    /// How many filters the user chose. The region preference and the price slider's default
+    /// maximum don't count.
+    fn chosen_filter_count(&self) -> usize {
+        self.requested_filters()
+            .into_iter()
+            .filter(|f| !matches!(*f, "name" | "region"))
+            .count()
+    }
+
+    /// Whether this request is just browsing the whole catalog, with no query or filters
+    pub fn is_browsing_all(&self) -> bool {
+        self.name.is_none() && self.chosen_filter_count() == 0
+    }
+
+    /// Heading for the results, saying what was searched for
+    pub fn results_heading(&self) -> String {
+        let filters = match self.chosen_filter_count() {
+            1 => "1 filter".to_string(),
+            n => format!("{n} filters"),
+        };
+
+        match &self.name {
+            _ if self.is_browsing_all() => "Browse all filament".to_string(),
+            Some(name) if self.chosen_filter_count() == 0 => {
+                format!("Search results for “{name}”")
+            }
+            Some(name) => format!("Search results for “{name}” with {filters}"),
+            None => format!("Search results for {filters}"),
+        }
+    }
+}
+
+/// How the user's region narrows or orders the results
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionMode {
+    #[default]
+    Anywhere,
+    /// Listings that ship to the region first
+    Prefer,
+    /// Only listings that ship to the region
+    Only,
+}
+
+/// Removes products whose UUID already appeared earlier in the list, keeping the first, and
+/// returns how many were removed. Rows are keyed by UUID, so duplicates would confuse rendering.
+fn dedupe_by_uuid(products: &mut Vec<Product>) -> usize {
+    let before = products.len();
+    let mut seen = HashSet::new();
+
+    products.retain(|p| seen.insert(p.uuid.clone()));
+    before - products.len()
+}
+
+/// Applies the region preference to a page of results, in case the backend didn't.
+/// Listings with unknown shipping regions are kept unless `hide_unknown`.
+fn apply_region(products: &mut Vec<Product>, region: Region, mode: RegionMode, hide_unknown: bool) {
+    match mode {
+        RegionMode::Anywhere => {}
+        RegionMode::Prefer => {
+            // Stable, so the requested sort still holds within each group
+            products.sort_by_key(|p| match p.ships_to_region(region) {
+                Some(true) => 0,
+                None => 1,
+                Some(false) => 2,
+            });
+        }
+        RegionMode::Only => {
+            products.retain(|p| p.ships_to_region(region).unwrap_or(!hide_unknown));
+        }
+    }
+}
+
+/// Whether a product needs any or all of the requested tags to match
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TagMatch {
+    #[default]
+    Any,
+    All,
+}
+
+/// Everything the search form can filter and sort by, independent of the signals holding it
+#[derive(Clone, Debug, PartialEq)]
+pub struct FilterState {
+    pub query: String,
+    /// Whole units of `currency`
+    pub min_price: u32,
+    /// Whole units of `currency`; [`MAX_PRICE_CAP`] means no upper limit was chosen
+    pub max_price: u32,
+    /// The currency of the price range
+    pub currency: Currency,
+    pub material: MaterialFilter,
+    pub color: ColorFilter,
+    pub diameter: DiameterFilter,
+    pub weight: WeightFilter,
+    pub on_sale_only: bool,
+    pub exclude_unspecified_material: bool,
+    pub exclude_unspecified_color: bool,
+    /// Materials to leave out; only applied while no single material is chosen
+    pub exclude_materials: Vec<FilamentMaterial>,
+    pub tags: Vec<String>,
+    pub tag_match: TagMatch,
+    pub sort: Vec<SortKey>,
+}
+
+impl FilterState {
+    /// The request for one page of results matching these filters
+    pub fn to_request(&self, page: u32, per_page: u32) -> ProductSearchRequest {
+        let query = self.query.trim();
+
+        ProductSearchRequest {
+            name: (!query.is_empty()).then(|| query.to_string()),
+            min_price: Cents::from_whole_dollars(self.min_price),
+            max_price: Cents::from_whole_dollars(self.max_price),
+            currency: Some(self.currency),
+            material: self.material.to_material(),
+            materials: self.material.to_materials(),
+            color: self.color.to_color(),
+            diameter: self.diameter.to_diameter(),
+            weight: self.weight.to_weight(),
+            page,
+            per_page,
+            sort_by: self.sort.first().map(|k| k.by),
+            sort: self.sort.clone(),
+            on_sale: self.on_sale_only.then_some(true),
+            tags: Some(self.tags.clone()).filter(|t| !t.is_empty()),
+            tag_match: Some(self.tag_match),
+            region: None,
+            region_mode: None,
+            hide_unknown_region: None,
+            exclude_unspecified_material: self.exclude_unspecified_material.then_some(true),
+            exclude_materials: Some(self.exclude_materials.clone())
+                .filter(|m| !m.is_empty() && self.material == MaterialFilter::Any),
+            exclude_unspecified_color: self.exclude_unspecified_color.then_some(true),
+            aggregates: None,
+        }
+    }
+
+    /// Encodes the filters as a URL query string without the leading `?`. Values at their defaults
+    /// are left out, so browsing everything gives an empty string.
+    pub fn to_query(&self) -> String {
+        let defaults = FilterState::default();
+        let mut params: Vec<(&str, String)> = vec![];
+
+        let query = self.query.trim();
+        if !query.is_empty() {
+            params.push(("q", query.to_string()));
+        }
+        if self.min_price != defaults.min_price {
+            params.push(("min_price", self.min_price.to_string()));
+        }
+        if self.max_price != defaults.max_price {
+            params.push(("max_price", self.max_price.to_string()));
+        }
+        if self.currency != defaults.currency {
+            params.push(("cur", self.currency.code().to_string()));
+        }
+        if self.material != MaterialFilter::Any {
+            params.push(("mat", self.material.to_string()));
+        }
+        if self.color != ColorFilter::Any {
+            params.push(("col", self.color.to_string()));
+        }
+        if self.diameter != DiameterFilter::Any {
+            params.push(("diam", self.diameter.to_string()));
+        }
+        if self.weight != WeightFilter::Any {
+            params.push(("weight", self.weight.to_string()));
+        }
+        if self.on_sale_only {
+            params.push(("sale", "1".to_string()));
+        }
+        if self.exclude_unspecified_material {
+            params.push(("known_mat", "1".to_string()));
+        }
+        if self.exclude_unspecified_color {
+            params.push(("known_col", "1".to_string()));
+        }
+        if !self.exclude_materials.is_empty() {
+            let names: Vec<String> = self
+                .exclude_materials
+                .iter()
+                .map(|m| m.to_string())
+                .collect();
+            params.push(("xmat", names.join(",")));
+        }
+        if !self.tags.is_empty() {
+            params.push(("tags", self.tags.join(",")));
+
+            if self.tag_match == TagMatch::All {
+                params.push(("tag_match", "all".to_string()));
+            }
+        }
+        if self.sort != defaults.sort {
+            match self.sort.as_slice() {
+                // A single key is written as `sortby`, with `dir=desc` when descending
+                [key] => {
+                    params.push(("sortby", key.by.name().to_string()));
+                    if key.direction == SortDirection::Desc {
+                        params.push(("dir", "desc".to_string()));
+                    }
+                }
+                // Several as `sort`, each prefixed with `-` when descending
+                keys => {
+                    let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+                    params.push(("sort", keys.join(",")));
+                }
+            }
+        }
+
+        params
+            .into_iter()
+            .map(|(key, value)| format!("{key}={}", encode_query_value(&value)))
+            .collect::<Vec<_>>()
+            .join("&")
+    }
+
+    /// Reads filters back from a URL query string (with or without the leading `?`). Missing or
+    /// malformed parameters keep their defaults.
+    pub fn from_query(query: &str) -> Self {
+        let mut filters = FilterState::default();
+        let params = parse_query(query);
+        let get = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        if let Some(q) = get("q") {
+            filters.query = q.to_string();
+        }
+        // Malformed or negative numbers are ignored rather than replaced with a default
+        if let Some(v) = get("min_price").and_then(parse_url_number) {
+            filters.min_price = v.min(MAX_PRICE_CAP as u64) as u32;
+        }
+        if let Some(v) = get("max_price").and_then(parse_url_number) {
+            filters.max_price = v.min(MAX_PRICE_CAP as u64) as u32;
+        }
+        // Kept as a range the slider can show, e.g. `?max_price=0` becomes 0–1
+        filters.max_price = filters.max_price.max(PRICE_GAP);
+        filters.min_price = filters.min_price.min(filters.max_price - PRICE_GAP);
+        if let Some(c) = get("cur").and_then(|v| v.parse().ok()) {
+            filters.currency = c;
+        }
+        if let Some(m) = get("mat").and_then(|v| v.parse().ok()) {
+            filters.material = m;
+        }
+        if let Some(c) = get("col").and_then(|v| v.parse().ok()) {
+            filters.color = c;
+        }
+        if let Some(d) = get("diam").and_then(|v| v.parse().ok()) {
+            filters.diameter = d;
+        }
+        if let Some(w) = get("weight").and_then(|v| v.parse().ok()) {
+            filters.weight = w;
+        }
+        filters.on_sale_only = get("sale") == Some("1");
+        filters.exclude_unspecified_material = get("known_mat") == Some("1");
+        filters.exclude_unspecified_color = get("known_col") == Some("1");
+        if let Some(v) = get("xmat") {
+            filters.exclude_materials = KNOWN_MATERIALS
+                .iter()
+                .filter(|m| v.split(',').any(|name| m.to_string() == name))
+                .cloned()
+                .collect();
+        }
+        if let Some(v) = get("tags") {
+            filters.tags = v.split(',').filter_map(normalize_tag).collect();
+        }
+        if get("tag_match") == Some("all") {
+            filters.tag_match = TagMatch::All;
+        }
+        if let Some(v) = get("sort") {
+            let keys: Vec<SortKey> = v
+                .split(',')
+                .filter_map(|k| k.parse().ok())
+                .take(MAX_SORT_KEYS)
+                .collect();
+
+            if !keys.is_empty() {
+                filters.sort = keys;
+            }
+        } else if let Some(by) = get("sortby").and_then(|v| v.parse::<SortBy>().ok()) {
+            let direction = match get("dir") {
+                Some("desc") => SortDirection::Desc,
+                _ => SortDirection::Asc,
+            };
+            filters.sort = vec![SortKey { by, direction }];
+        }
+
+        filters
+    }
+}
+
+impl Default for FilterState {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            min_price: 0,
+            max_price: MAX_PRICE_CAP,
+            currency: Currency::default(),
+            material: MaterialFilter::Any,
+            color: ColorFilter::Any,
+            diameter: DiameterFilter::Any,
+            weight: WeightFilter::Any,
+            on_sale_only: false,
+            exclude_unspecified_material: false,
+            exclude_unspecified_color: false,
+            exclude_materials: vec![],
+            tags: vec![],
+            tag_match: TagMatch::Any,
+            sort: vec![SortKey::asc(SortBy::PricePerKg)],
+        }
+    }
+}
+
+/// The page number in a URL query string, if it has a valid one
+fn query_page(query: &str) -> Option<u32> {
+    parse_query(query)
+        .into_iter()
+        .find(|(key, _)| key == "page")
+        .and_then(|(_, value)| parse_url_number(&value))
+        .map(|n| n.clamp(1, u32::MAX as u64) as u32)
+}
+
+/// Splits a query string into decoded key/value pairs, in order
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .trim_start_matches('?')
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (decode_query_value(key), decode_query_value(value))
+        })
+        .collect()
+}
+
+/// Percent-encodes everything but unreserved characters, so the value is safe in a query string
+fn encode_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+/// Reverses [`encode_query_value`], also accepting `+` for a space as browsers write it.
+/// Invalid escapes are kept as-is.
+fn decode_query_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |b: u8| (b as char).to_digit(16);
+
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        out.push((high * 16 + low) as u8);
+                        i += 2;
+                    }
+                    _ => out.push(b'%'),
+                }
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
 }
 
 const PER_PAGE: u32 = 50;
 
+/// A non-negative whole number from a URL parameter. Values too large for a `u64` saturate, so an
+/// absurd `?min_price=` still reads as "very large" for the caller to clamp.
+fn parse_url_number(s: &str) -> Option<u64> {
+    let s = s.trim();
+
+    if s.is_empty() || !s.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some(s.parse::<u64>().unwrap_or(u64::MAX))
+}
+
 #[component]
 pub fn ProductSearch() -> impl IntoView {
     let (seeking, set_seeking) = signal(true);
     let (results, set_results) = signal::<Vec<Product>>(vec![]);
-    let (query, set_query) = signal(String::new());
-    let (mat_filter, set_mat_filter) = signal::<MaterialFilter>(MaterialFilter::Any);
-    let (col_filter, set_col_filter) = signal::<ColorFilter>(ColorFilter::Any);
-    let (diam_filter, set_diam_filter) = signal::<DiameterFilter>(DiameterFilter::Any);
-    let (weight_filter, set_weight_filter) = signal::<WeightFilter>(WeightFilter::Any);
-    let (sortby, set_sortby) = signal::<SortBy>(SortBy::PricePerKg);
+    let filters = RwSignal::new(FilterState::default());
+    // Each control reads and writes its own slice, so changing one doesn't wake the others
+    let (query, set_query) = create_slice(filters, |f| f.query.clone(), |f, v| f.query = v);
+    let (mat_filter, set_mat_filter) =
+        create_slice(filters, |f| f.material.clone(), |f, v| f.material = v);
+    let (col_filter, set_col_filter) =
+        create_slice(filters, |f| f.color.clone(), |f, v| f.color = v);
+    let (diam_filter, set_diam_filter) =
+        create_slice(filters, |f| f.diameter.clone(), |f, v| f.diameter = v);
+    let (weight_filter, set_weight_filter) =
+        create_slice(filters, |f| f.weight.clone(), |f, v| f.weight = v);
+    let (sort_keys, set_sort_keys) = create_slice(filters, |f| f.sort.clone(), |f, v| f.sort = v);
+    let (on_sale_only, set_on_sale_only) =
+        create_slice(filters, |f| f.on_sale_only, |f, v| f.on_sale_only = v);
+    let (hide_unspecified_mat, set_hide_unspecified_mat) = create_slice(
+        filters,
+        |f| f.exclude_unspecified_material,
+        |f, v| f.exclude_unspecified_material = v,
+    );
+    let (exclude_materials, set_exclude_materials) = create_slice(
+        filters,
+        |f| f.exclude_materials.clone(),
+        |f, v| f.exclude_materials = v,
+    );
+    let (hide_unspecified_col, set_hide_unspecified_col) = create_slice(
+        filters,
+        |f| f.exclude_unspecified_color,
+        |f, v| f.exclude_unspecified_color = v,
+    );
+    let (tags, set_tags) = create_slice(filters, |f| f.tags.clone(), |f, v| f.tags = v);
+    let (tag_match, set_tag_match) = create_slice(filters, |f| f.tag_match, |f, v| f.tag_match = v);
 
     let (page, set_page) = signal(1u32);
     let (total_pages, set_total_pages) = signal(1u32);
     let (total_results, set_total_results) = signal(0u32);
-
-    let (min_price_int, set_min_price_int) = signal(0u32);
-    let (max_price_int, set_max_price_int) = signal(100u32);
+    let (aggregates, set_aggregates) = signal::<Option<SearchAggregates>>(None);
+    let (unapplied, set_unapplied) = signal::<Vec<&'static str>>(vec![]);
+
+    let (watching, set_watching) = signal(false);
+    let prefs = use_preferences();
+    let watch_minutes = move || prefs.with(|p| p.watch_minutes);
+    let (new_results, set_new_results) = signal(0usize);
+    let last_request = StoredValue::new(None::<ProductSearchRequest>);
+    // Set from each search, so it describes what the results are for rather than unsaved edits
+    let (heading, set_heading) = signal::<Option<(String, bool)>>(None);
+
+    let (min_price_int, set_min_price_int) =
+        create_slice(filters, |f| f.min_price, |f, v| f.min_price = v);
+    let (max_price_int, set_max_price_int) =
+        create_slice(filters, |f| f.max_price, |f, v| f.max_price = v);
+    let (currency, set_currency) = create_slice(filters, |f| f.currency, |f, v| f.currency = v);
 
     let loc = leptos_router::hooks::use_location();
     let navigate = leptos_router::hooks::use_navigate();
@@ -214,191 +1055,443 @@ pub fn ProductSearch() -> impl IntoView {
     // Parse from URL
     Effect::new(move |_| {
         let search = loc.search.get_untracked();
-        if let Ok(params) = web_sys::UrlSearchParams::new_with_str(&search) {
-            if let Some(q) = params.get("q") {
-                set_query.set(q);
-            }
-            if let Some(p) = params.get("page")
-                && let Ok(n) = p.parse::<u32>()
-            {
-                set_page.set(n);
-            }
-            if let Some(v) = params.get("min_price") {
-                set_min_price_int.set(v.parse::<u32>().unwrap_or(0).clamp(0, MAX_PRICE_CAP));
-            }
-            if let Some(v) = params.get("max_price") {
-                set_max_price_int.set(
-                    v.parse::<u32>()
-                        .unwrap_or(MAX_PRICE_CAP)
-                        .clamp(0, MAX_PRICE_CAP),
-                );
-            }
-            if let Some(v) = params.get("mat")
-                && let Ok(m) = v.parse::<MaterialFilter>()
-            {
-                set_mat_filter.set(m);
-            }
-            if let Some(v) = params.get("col")
-                && let Ok(c) = v.parse::<ColorFilter>()
-            {
-                set_col_filter.set(c);
-            }
-            if let Some(v) = params.get("diam")
-                && let Ok(d) = v.parse::<DiameterFilter>()
-            {
-                set_diam_filter.set(d);
-            }
-            if let Some(v) = params.get("weight")
-                && let Ok(w) = v.parse::<WeightFilter>()
-            {
-                set_weight_filter.set(w);
-            }
-            if let Some(v) = params.get("sortby")
-                && let Ok(s) = serde_json::from_str::<SortBy>(&format!("\"{}\"", v))
-            {
-                set_sortby.set(s);
-            }
+
+        filters.set(FilterState::from_query(&search));
+        if let Some(page) = query_page(&search) {
+            set_page.set(page);
         }
     });
 
-    // Write to URL
-    Effect::new(move |_| {
-        let params = web_sys::UrlSearchParams::new().unwrap();
-
-        let query = query.get_untracked();
-        let query = query.trim();
-        if !query.is_empty() {
-            params.set("q", query);
-        }
+    // The filters the shown results were searched with, to tell when the form has moved on
+    let searched_filters = RwSignal::new(None::<FilterState>);
+    let filters_changed = Memo::new(move |_| {
+        searched_filters.with(|s| s.as_ref().is_some_and(|s| filters.with(|f| f != s)))
+    });
 
-        let min = min_price_int.get_untracked();
-        if min != 0 {
-            params.set("min_price", &min.to_string());
-        }
+    // Write to URL after each search, so it describes the shown results rather than unsaved
+    // edits. Writes in quick succession are coalesced, and only an explicit search or a page
+    // change adds a history entry; sorting and filter tweaks replace the current one, so Back
+    // isn't filled with every step of a slider drag.
+    let url_debounce = StoredValue::new(None::<TimeoutHandle>);
+    let url_history = StoredValue::new(UrlHistory::new(page.get_untracked()));
 
-        let max = max_price_int.get_untracked();
-        if max != MAX_PRICE_CAP {
-            params.set("max_price", &max.to_string());
-        }
+    Effect::new(move |_| {
+        let Some(mut query) = searched_filters.with(|s| s.as_ref().map(|f| f.to_query())) else {
+            return;
+        };
 
-        let mat_filter = mat_filter.get_untracked();
-        if mat_filter != MaterialFilter::Any {
-            params.set("mat", &mat_filter.to_string());
+        if let Some(handle) = url_debounce.get_value() {
+            handle.clear();
         }
 
-        let col_filter = col_filter.get_untracked();
-        if col_filter != ColorFilter::Any {
-            params.set("col", &col_filter.to_string());
-        }
+        let navigate = navigate.clone();
+        let write = move || {
+            let page = page.get_untracked();
+            if page != 1 {
+                if !query.is_empty() {
+                    query.push('&');
+                }
+                query.push_str(&format!("page={page}"));
+            }
 
-        let diam_filter = diam_filter.get_untracked();
-        if diam_filter != DiameterFilter::Any {
-            params.set("diam", &diam_filter.to_string());
-        }
+            let push = url_history
+                .try_update_value(|h| h.is_push(page))
+                .unwrap_or(false);
 
-        let weight_filter = weight_filter.get_untracked();
-        if weight_filter != WeightFilter::Any {
-            params.set("weight", &weight_filter.to_string());
-        }
+            if loc
+                .search
+                .with_untracked(|s| s.trim_start_matches('?') == query)
+            {
+                return;
+            }
 
-        let page = page.get();
-        if page != 1 {
-            params.set("page", &page.to_string());
-        }
+            navigate(
+                &format!("?{query}"),
+                leptos_router::NavigateOptions {
+                    replace: !push,
+                    ..Default::default()
+                },
+            );
+        };
 
-        let sortby = sortby.get();
-        if sortby != SortBy::PricePerKg
-            && let Ok(s) = serde_json::to_string(&sortby)
-        {
-            params.set("sortby", s.trim_matches('"'));
-        }
-        navigate(&format!("?{}", params.to_string()), Default::default());
+        url_debounce
+            .set_value(set_timeout_with_handle(write, Duration::from_millis(URL_DEBOUNCE_MS)).ok());
     });
 
+    // The page the results were last fetched for, so moving to another page searches once
+    let prev_page = StoredValue::new(page.get_untracked());
+    // Goes back to page 1 without the page change starting a search of its own
+    let reset_page = move || {
+        prev_page.set_value(1);
+        set_page.set(1);
+    };
+
     let search = {
         move || {
-            let query = if query.get_untracked().trim().is_empty() {
-                None
-            } else {
-                Some(query.get_untracked().trim().to_string())
-            };
+            let current = filters.get_untracked();
+            let requested = page.get_untracked();
+            if searched_filters.with_untracked(|s| search_page(s.as_ref(), &current, requested))
+                != requested
+            {
+                reset_page();
+            }
+            searched_filters.set(Some(current));
+            let payload = filters
+                .with_untracked(|f| f.to_request(page.get_untracked(), PER_PAGE))
+                .with_region_preference(&prefs.get_untracked())
+                .with_stats_preference(&prefs.get_untracked());
 
-            let payload: ProductSearchRequest = ProductSearchRequest {
-                name: query,
-                min_price: Some(Cents(min_price_int.get_untracked() * 100)),
-                max_price: Some(Cents(max_price_int.get_untracked() * 100)),
-                material: match mat_filter.get_untracked() {
-                    MaterialFilter::Any => None,
-                    MaterialFilter::Material(m) => Some(m.clone()),
-                    MaterialFilter::Other(s) => {
-                        if s.trim().is_empty() {
-                            None
-                        } else {
-                            Some(FilamentMaterial::Other(s.trim().to_string()))
-                        }
-                    }
-                    MaterialFilter::Unspecified => Some(FilamentMaterial::Unspecified),
-                },
-                color: match col_filter.get_untracked() {
-                    ColorFilter::Any => None,
-                    ColorFilter::Material(c) => Some(c.clone()),
-                    ColorFilter::Other(s) => {
-                        if s.trim().is_empty() {
-                            None
-                        } else {
-                            Some(FilamentColor::Other(s.trim().to_string()))
-                        }
-                    }
-                    ColorFilter::Unspecified => Some(FilamentColor::Unspecified),
-                },
-                diameter: match diam_filter.get_untracked() {
-                    DiameterFilter::Any => None,
-                    DiameterFilter::D175 => Some(FilamentDiameter::D175),
-                    DiameterFilter::D285 => Some(FilamentDiameter::D285),
-                    DiameterFilter::Other(s) => {
-                        if s.trim().is_empty() {
-                            None
-                        } else {
-                            Some(FilamentDiameter::from_mm_string(&s))
-                        }
-                    }
-                },
-                weight: match weight_filter.get_untracked() {
-                    WeightFilter::Any => None,
-                    WeightFilter::G500 => Some(Grams(500)),
-                    WeightFilter::G750 => Some(Grams(750)),
-                    WeightFilter::G1000 => Some(Grams(1000)),
-                    WeightFilter::G2000 => Some(Grams(2000)),
-                    WeightFilter::Other(s) => {
-                        if s.trim().is_empty() {
-                            None
-                        } else {
-                            Some(Grams::from_kg_string(&s))
-                        }
-                    }
-                },
-                page: page.get_untracked(),
-                per_page: PER_PAGE,
-                sort_by: Some(sortby.get_untracked()),
-            };
+            last_request.set_value(Some(payload.clone()));
+            set_heading.set(Some((payload.results_heading(), payload.is_browsing_all())));
+            set_new_results.set(0);
 
             spawn_local(async move {
                 set_seeking.set(true);
-                let response = search_products(&payload).await;
+                let mut response = search_products(&payload).await;
+
+                // A page past the end (e.g. a stale `?page=99999` link) can't be known to be
+                // out of range until the total arrives; moving to the last page refetches it
+                // and updates the URL. The out-of-range page's own write is dropped, or
+                // replaced if it already happened, so Back doesn't return to it.
+                if let Some(last_page) = page_past_end(payload.page, response.total_pages) {
+                    if let Some(handle) = url_debounce.get_value() {
+                        handle.clear();
+                    }
+                    url_history.update_value(|h| h.correct_page(last_page));
+                    set_total_pages.set(last_page);
+                    set_page.set(last_page);
+                    return;
+                }
+
+                let duplicates = dedupe_by_uuid(&mut response.items);
+                if duplicates > 0 {
+                    crate::console_warn(format!(
+                        "Search returned {duplicates} product(s) with a duplicate UUID; showing the first of each"
+                    ));
+                }
+
+                set_unapplied.set(payload.unapplied_filters(&response));
+                sort_products(&mut response.items, &payload.sort);
+                if let (Some(region), Some(mode)) = (payload.region, payload.region_mode) {
+                    let hide_unknown = payload.hide_unknown_region.unwrap_or(false);
+                    apply_region(&mut response.items, region, mode, hide_unknown);
+                }
                 set_results.set(response.items);
                 set_total_pages.set(response.total_pages as u32);
                 set_total_results.set(response.total as u32);
+                set_aggregates.set(response.aggregates);
                 set_seeking.set(false);
             });
         }
     };
 
-    let on_search = move |_| {
-        set_page.set(1);
+    let seek = move || {
+        reset_page();
         search();
     };
 
-    let prev_page = StoredValue::new(page.get_untracked());
+    // A search the user asked for, which gets its own history entry
+    let search_now = move || {
+        url_history.update_value(UrlHistory::push_next);
+        seek();
+    };
+
+    let on_search = move |_| search_now();
+
+    let stale_hint = move || {
+        filters_changed.get().then(|| {
+            view! { <small class="hint" role="status">"Filters changed — click Seek to update"</small> }
+        })
+    };
+
+    // On phones the filters live in a drawer, leaving the results near the top of the page
+    let viewport_width = use_viewport_width();
+    let narrow = Memo::new(move |_| viewport_width.get() <= NARROW_MAX_WIDTH);
+    let filters_open = RwSignal::new(false);
+    let filter_count =
+        Memo::new(move |_| filters.with(|f| f.to_request(1, PER_PAGE).chosen_filter_count()));
+
+    let filter_controls = move || {
+        view! {
+            <FilterBar
+                mat_filter=mat_filter
+                set_mat_filter=set_mat_filter
+                col_filter=col_filter
+                set_col_filter=set_col_filter
+                diam_filter=diam_filter
+                set_diam_filter=set_diam_filter
+                weight_filter=weight_filter
+                set_weight_filter=set_weight_filter
+            />
+
+            <MaterialsLegend on_select=move |m: FilamentMaterial| set_mat_filter.set(MaterialFilter::Materials(vec![m])) />
+
+            <label class="checkbox-label">
+                <input
+                    type="checkbox"
+                    prop:checked=move || on_sale_only.get()
+                    on:change=move |e| set_on_sale_only.set(event_target_checked(&e))
+                />
+                "On sale only"
+            </label>
+
+            <div class="checkbox-label">
+                <select
+                    class="input"
+                    style="width: auto;"
+                    aria-label="Region filter"
+                    prop:value=move || match prefs.with(|p| p.region_mode) {
+                        RegionMode::Anywhere => "anywhere",
+                        RegionMode::Prefer => "prefer",
+                        RegionMode::Only => "only",
+                    }
+                    on:change=move |e| {
+                        let mode = match event_target_value(&e).as_str() {
+                            "prefer" => RegionMode::Prefer,
+                            "only" => RegionMode::Only,
+                            _ => RegionMode::Anywhere,
+                        };
+                        prefs.update(|p| p.region_mode = mode);
+                    }
+                >
+                    <option value="anywhere">"Ships anywhere"</option>
+                    <option value="prefer">"Prefer shipping to"</option>
+                    <option value="only">"Only shipping to"</option>
+                </select>
+                <select
+                    class="input"
+                    style="width: auto;"
+                    aria-label="Your region"
+                    prop:value=move || prefs.with(|p| p.region.map(|r| r.code()).unwrap_or_default())
+                    on:change=move |e| {
+                        let region = Region::from_code(&event_target_value(&e));
+                        prefs.update(|p| p.region = region);
+                    }
+                >
+                    <option value="">"Choose region…"</option>
+                    {Region::iter()
+                        .map(|r| view! { <option value=r.code()>{r.to_string()}</option> })
+                        .collect_view()}
+                </select>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || prefs.with(|p| p.hide_unknown_region)
+                        on:change=move |e| {
+                            let checked = event_target_checked(&e);
+                            prefs.update(|p| p.hide_unknown_region = checked);
+                        }
+                    />
+                    "Hide unknown shipping"
+                </label>
+            </div>
+
+            <div class="checkbox-label">
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || hide_unspecified_mat.get()
+                        on:change=move |e| set_hide_unspecified_mat.set(event_target_checked(&e))
+                    />
+                    "Exclude unspecified material"
+                </label>
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || hide_unspecified_col.get()
+                        on:change=move |e| set_hide_unspecified_col.set(event_target_checked(&e))
+                    />
+                    "Exclude unspecified color"
+                </label>
+            </div>
+
+            <ExcludeMaterials
+                excluded=exclude_materials
+                set_excluded=set_exclude_materials
+                active=Signal::derive(move || mat_filter.get() == MaterialFilter::Any)
+            />
+
+            <div class="options-row">
+                <div>
+                    <label>"Tags"</label>
+                    <TagInput tags=tags set_tags=set_tags placeholder="e.g. silk, high-speed" />
+                </div>
+                <div style="flex: 0 0 auto;">
+                    <label>"Match"</label>
+                    <select
+                        class="input"
+                        prop:value=move || match tag_match.get() {
+                            TagMatch::Any => "any",
+                            TagMatch::All => "all",
+                        }
+                        on:change=move |e| {
+                            set_tag_match.set(if event_target_value(&e) == "all" {
+                                TagMatch::All
+                            } else {
+                                TagMatch::Any
+                            })
+                        }
+                    >
+                        <option value="any">"Any tag"</option>
+                        <option value="all">"All tags"</option>
+                    </select>
+                </div>
+            </div>
+
+            <div class="checkbox-label">
+                <label class="checkbox-label">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || watching.get()
+                        on:change=move |e| set_watching.set(event_target_checked(&e))
+                    />
+                    "Watch for new results every"
+                </label>
+                <select
+                    class="input"
+                    style="width: auto;"
+                    aria-label="Watch interval"
+                    prop:value=move || watch_minutes().to_string()
+                    on:change=move |e| {
+                        if let Ok(m) = event_target_value(&e).parse::<u32>() {
+                            prefs.update(|p| p.watch_minutes = m);
+                        }
+                    }
+                >
+                    {WATCH_INTERVALS_MINS
+                        .iter()
+                        .map(|m| view! { <option value=m.to_string()>{format!("{m} min")}</option> })
+                        .collect_view()}
+                </select>
+            </div>
+        }
+    };
+
+    let price_slider = move || {
+        view! {
+            <RangeSlider
+                min_value=min_price_int
+                set_min_value=set_min_price_int
+                max_value=max_price_int
+                set_max_value=set_max_price_int
+                currency=currency
+                min_limit=0
+                max_limit=MAX_PRICE_CAP
+                step=1
+                gap=PRICE_GAP
+            />
+        }
+    };
+
+    // Apply the price range once the handles settle, rather than on every step of a drag
+    let price_debounce = StoredValue::new(None::<TimeoutHandle>);
+
+    Effect::new(move |prev: Option<(u32, u32)>| {
+        let current = (min_price_int.get(), max_price_int.get());
+
+        if prev.is_some_and(|p| p != current) {
+            if let Some(handle) = price_debounce.get_value() {
+                handle.clear();
+            }
+
+            let handle =
+                set_timeout_with_handle(seek, Duration::from_millis(PRICE_DEBOUNCE_MS)).ok();
+            price_debounce.set_value(handle);
+        }
+
+        current
+    });
+
+    // Re-run the last search in the background while watching, and count results not yet shown
+    let poll_handle = StoredValue::new(None::<IntervalHandle>);
+
+    Effect::new(move |_| {
+        if let Some(handle) = poll_handle.get_value() {
+            handle.clear();
+        }
+        poll_handle.set_value(None);
+
+        if !watching.get() {
+            return;
+        }
+
+        let interval = Duration::from_secs(watch_minutes() as u64 * 60);
+
+        let handle = set_interval_with_handle(
+            move || {
+                if document().hidden() || seeking.get_untracked() {
+                    return;
+                }
+
+                let Some(request) = last_request.get_value() else {
+                    return;
+                };
+
+                spawn_local(async move {
+                    let response = search_products(&request).await;
+
+                    let new_count = results.with_untracked(|current| {
+                        response
+                            .items
+                            .iter()
+                            .filter(|p| !current.iter().any(|c| c.uuid == p.uuid))
+                            .count()
+                    });
+
+                    set_new_results.set(new_count);
+                });
+            },
+            interval,
+        )
+        .ok();
+
+        poll_handle.set_value(handle);
+    });
+
+    on_cleanup(move || {
+        if let Some(handle) = poll_handle.get_value() {
+            handle.clear();
+        }
+    });
+
+    let search_input = NodeRef::<html::Input>::new();
+    // Read by screen readers when keyboard shortcuts change things that aren't otherwise announced
+    let (announcement, set_announcement) = signal(String::new());
+
+    let shortcut_handle = window_event_listener(ev::keydown, move |e| {
+        if shortcuts::should_ignore(&e) {
+            return;
+        }
+
+        match e.key().as_str() {
+            "/" => {
+                if let Some(input) = search_input.get_untracked() {
+                    e.prevent_default();
+                    let _ = input.focus();
+                }
+            }
+            "ArrowLeft" if !seeking.get_untracked() && page.get_untracked() > 1 => {
+                set_page.set(page.get_untracked() - 1);
+            }
+            "ArrowRight"
+                if !seeking.get_untracked()
+                    && page.get_untracked() < total_pages.get_untracked() =>
+            {
+                set_page.set(page.get_untracked() + 1);
+            }
+            "s" => {
+                let by = match sort_keys.with_untracked(|k| k.first().map(|k| k.by)) {
+                    Some(SortBy::PricePerKg) => SortBy::Price,
+                    _ => SortBy::PricePerKg,
+                };
+
+                set_sort_keys.set(vec![SortKey::asc(by)]);
+                set_announcement.set(format!("Sorted by {}", by.spoken_name()));
+            }
+            _ => (),
+        }
+    });
+    on_cleanup(move || shortcut_handle.remove());
 
     Effect::new(move |_| {
         let current = page.get();
@@ -416,7 +1509,7 @@ pub fn ProductSearch() -> impl IntoView {
     });
 
     Effect::new(move |_| {
-        let _ = sortby.get();
+        let _ = sort_keys.get();
         search();
     });
 
@@ -431,254 +1524,475 @@ pub fn ProductSearch() -> impl IntoView {
                         class="input"
                         type="text"
                         placeholder="Search by name…"
+                        node_ref=search_input
                         prop:value=move || query.get()
                         on:input=move |e| set_query.set(event_target_value(&e))
+                        on:keydown=move |e| {
+                            if e.key() == "Enter" {
+                                search_now();
+                            }
+                        }
                     />
-                </div>
-                <div class="options-row">
-                    <div>
-                        <label>"Material"</label>
-                        <select
-                            class="input"
-                            prop:value=move || match mat_filter.get() {
-                                MaterialFilter::Any => "Any".to_string(),
-                                MaterialFilter::Unspecified => "Unspecified".to_string(),
-                                MaterialFilter::Other(_) => "Other".to_string(),
-                                MaterialFilter::Material(m) => m.to_string(),
+                    <select
+                        class="input"
+                        style="width: auto;"
+                        aria-label="Currency"
+                        prop:value=move || currency.get().code()
+                        on:change=move |e| {
+                            if let Ok(c) = event_target_value(&e).parse() {
+                                set_currency.set(c);
                             }
-                            on:change=move |e| {
-                                let v = event_target_value(&e);
-
-                                match v.as_str() {
-                                    "Any" => set_mat_filter.set(MaterialFilter::Any),
-                                    "Unspecified" => set_mat_filter.set(MaterialFilter::Unspecified),
-                                    "Other" => set_mat_filter.set(MaterialFilter::Other(String::new())),
-                                    _ => {
-                                        let chosen = KNOWN_MATERIALS.iter()
-                                            .find(|m| m.to_string() == v)
-                                            .cloned();
-                                        if let Some(m) = chosen {
-                                            set_mat_filter.set(MaterialFilter::Material(m));
-                                        } else {
-                                            set_mat_filter.set(MaterialFilter::Any);
-                                        }
+                        }
+                    >
+                        {Currency::iter()
+                            .map(|c| view! { <option value=c.code()>{format!("{} {c}", c.symbol())}</option> })
+                            .collect_view()}
+                    </select>
+                </div>
+                {move || {
+                    if narrow.get() {
+                        view! {
+                            <div class="options-row seek-row">
+                                <button
+                                    class="filters-button"
+                                    aria-haspopup="dialog"
+                                    aria-label=move || match filter_count.get() {
+                                        0 => "Filters".to_string(),
+                                        n => format!("Filters, {n} active"),
                                     }
-                                }
+                                    on:click=move |_| filters_open.set(true)
+                                >
+                                    "Filters"
+                                    <Show when=move || filter_count.get() != 0>
+                                        <span class="filter-count">{move || filter_count.get()}</span>
+                                    </Show>
+                                </button>
+                                <button on:click=on_search>"Seek"</button>
+                            </div>
+                            {stale_hint}
+                            <Drawer open=filters_open title="Filters">
+                                {filter_controls()}
+                                {price_slider()}
+                                <button on:click=move |_| {
+                                    filters_open.set(false);
+                                    search_now();
+                                }>"Apply"</button>
+                            </Drawer>
+                        }
+                            .into_any()
+                    } else {
+                        view! {
+                            {filter_controls()}
+                            <div class="options-row seek-row">
+                                {price_slider()}
+                                <div style="justify-content: center; align-items: center;">
+                                    <button style="max-width: 400px" on:click=on_search>
+                                        "Seek"
+                                    </button>
+                                    {stale_hint}
+                                </div>
+                            </div>
+                        }
+                            .into_any()
+                    }
+                }}
+            </section>
+
+            <Show when=move || new_results.get() != 0>
+                <button class="banner" on:click=move |_| search()>
+                    {move || {
+                        let n = new_results.get();
+                        format!("{n} new result{} — refresh", if n == 1 { "" } else { "s" })
+                    }}
+                </button>
+            </Show>
+
+            <p class="sr-only" aria-live="polite">{move || announcement.get()}</p>
+
+            <Show when=move || !unapplied.with(|u| u.is_empty())>
+                <p class="price-note" role="status">
+                    {move || {
+                        let unapplied = unapplied.get();
+                        let noun = if unapplied.len() == 1 { "filter" } else { "filters" };
+                        let names = unapplied.join(", ").replace('_', " ");
+                        format!("The server didn't apply the {names} {noun}, so results may not match your selection")
+                    }}
+                </p>
+            </Show>
+
+            {move || {
+                heading
+                    .get()
+                    .map(|(text, browsing)| {
+                        view! { <h2 class="results-heading" class:browsing=browsing>{text}</h2> }
+                    })
+            }}
+            <div style="text-align: right;">
+                <SaveSearchButton filters=filters />
+            </div>
+
+            <section class="results">
+                {move || {
+                    if seeking.get() {
+                        view! { <div class="loading">"Seeking..."</div> }.into_any()
+                    } else if results.get().is_empty() {
+                        view! { <div class="empty">"No products match your filters."</div> }.into_any()
+                    } else {
+                        view! { <ProductTable
+                            products=results
+                            set_products=set_results
+                            page=page
+                            total_pages=total_pages
+                            set_page=set_page
+                            total_results=total_results
+                            per_page=PER_PAGE
+                            aggregates=aggregates
+                            currency=currency
+                            sort_keys=sort_keys
+                            set_sort_keys=set_sort_keys
+                        /> }.into_any()
+                    }
+                }}
+            </section>
+        </div>
+    }
+}
+
+/// Focuses a conditionally shown input once it has been rendered, e.g. after picking "Other…"
+pub fn focus_when_shown(input: NodeRef<html::Input>) {
+    request_animation_frame(move || {
+        if let Some(input) = input.get_untracked() {
+            let _ = input.focus();
+        }
+    });
+}
+
+/// Character count for a free-text material or color input, shown once it nears [`MAX_OTHER_LEN`]
+pub fn other_length_hint(value: &str) -> Option<String> {
+    let len = value.chars().count();
+    (len + 16 > MAX_OTHER_LEN).then(|| format!("{len}/{MAX_OTHER_LEN} characters"))
+}
+
+/// Material, color, diameter and weight filters, shared by the public search and the admin list
+#[component]
+pub fn FilterBar(
+    #[prop(into)] mat_filter: Signal<MaterialFilter>,
+    #[prop(into)] set_mat_filter: SignalSetter<MaterialFilter>,
+    #[prop(into)] col_filter: Signal<ColorFilter>,
+    #[prop(into)] set_col_filter: SignalSetter<ColorFilter>,
+    #[prop(into)] diam_filter: Signal<DiameterFilter>,
+    #[prop(into)] set_diam_filter: SignalSetter<DiameterFilter>,
+    #[prop(into)] weight_filter: Signal<WeightFilter>,
+    #[prop(into)] set_weight_filter: SignalSetter<WeightFilter>,
+) -> impl IntoView {
+    let mat_other_input = NodeRef::<html::Input>::new();
+    let col_other_input = NodeRef::<html::Input>::new();
+    let diam_other_input = NodeRef::<html::Input>::new();
+    let weight_other_input = NodeRef::<html::Input>::new();
+    let taxonomy = use_taxonomy();
+
+    // Adds or removes one material, keeping the taxonomy's order so the URL doesn't depend on
+    // the order they were clicked in
+    let toggle_material = move |m: FilamentMaterial, checked: bool| {
+        let mut chosen = match mat_filter.get_untracked() {
+            MaterialFilter::Materials(ms) => ms,
+            _ => vec![],
+        };
+        chosen.retain(|x| *x != m);
+        if checked {
+            chosen.push(m);
+        }
+        taxonomy.with_untracked(|t| {
+            chosen.sort_by_key(|x| t.materials.iter().position(|k| k == x));
+        });
+
+        set_mat_filter.set(if chosen.is_empty() {
+            MaterialFilter::Any
+        } else {
+            MaterialFilter::Materials(chosen)
+        });
+    };
+
+    view! {
+        <div class="options-row">
+            <div>
+                <label>"Material"</label>
+                <details class="multi-select">
+                    <summary class="input">
+                        {move || match mat_filter.get() {
+                            MaterialFilter::Any => "Any".to_string(),
+                            MaterialFilter::Unspecified => "Unspecified".to_string(),
+                            MaterialFilter::Other(_) => "Other".to_string(),
+                            MaterialFilter::Materials(ms) => {
+                                ms.iter().map(|m| m.to_string()).collect::<Vec<_>>().join(", ")
                             }
+                        }}
+                    </summary>
+                    <div class="multi-select-list" role="group" aria-label="Materials">
+                        <button
+                            type="button"
+                            class="link-button"
+                            on:click=move |_| set_mat_filter.set(MaterialFilter::Any)
                         >
-                            <option value="Any">"Any"</option>
-                            { KNOWN_MATERIALS.iter()
-                                .map(|m| {
-                                    let label = m.to_string();
-                                    view! { <option value=label.clone()>{ label.clone() }</option> }
-                                })
-                                .collect_view()
-                            }
-                            <option value="Unspecified">"Unspecified"</option>
-                            <option value="Other">"Other…"</option>
-                        </select>
-                        <Show when=move || matches!(mat_filter.get(), MaterialFilter::Other(_))>
-                            <input
-                                class="input"
-                                type="text"
-                                placeholder="Material name"
-                                on:input=move |e| {
-                                    set_mat_filter.update(|mf| {
-                                        if let MaterialFilter::Other(s) = mf {
-                                            *s = event_target_value(&e);
+                            "Any"
+                        </button>
+                        {move || {
+                            taxonomy.with(|t| {
+                                t.materials
+                                    .iter()
+                                    .map(|m| {
+                                        let label = m.to_string();
+                                        let m = m.clone();
+                                        let is_chosen = {
+                                            let m = m.clone();
+                                            move || {
+                                                mat_filter.with(|f| {
+                                                    matches!(f, MaterialFilter::Materials(ms) if ms.contains(&m))
+                                                })
+                                            }
+                                        };
+
+                                        view! {
+                                            <label class="checkbox-label">
+                                                <input
+                                                    type="checkbox"
+                                                    prop:checked=is_chosen
+                                                    on:change=move |e| toggle_material(m.clone(), event_target_checked(&e))
+                                                />
+                                                {label}
+                                            </label>
                                         }
+                                    })
+                                    .collect_view()
+                            })
+                        }}
+                        // Unspecified and Other are searched on their own, so choosing them clears the others
+                        <label class="checkbox-label">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || mat_filter.get() == MaterialFilter::Unspecified
+                                on:change=move |e| {
+                                    set_mat_filter.set(if event_target_checked(&e) {
+                                        MaterialFilter::Unspecified
+                                    } else {
+                                        MaterialFilter::Any
                                     });
                                 }
                             />
-                        </Show>
+                            "Unspecified"
+                        </label>
+                        <label class="checkbox-label">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || matches!(mat_filter.get(), MaterialFilter::Other(_))
+                                on:change=move |e| {
+                                    if event_target_checked(&e) {
+                                        set_mat_filter.set(MaterialFilter::Other(String::new()));
+                                        focus_when_shown(mat_other_input);
+                                    } else {
+                                        set_mat_filter.set(MaterialFilter::Any);
+                                    }
+                                }
+                            />
+                            "Other…"
+                        </label>
                     </div>
-                    <div>
-                        <label>"Color"</label>
-                        <select
-                            class="input"
-                            prop:value=move || match col_filter.get() {
-                                ColorFilter::Any => "Any".to_string(),
-                                ColorFilter::Unspecified => "Unspecified".to_string(),
-                                ColorFilter::Other(_) => "Other".to_string(),
-                                ColorFilter::Material(c) => c.to_string(),
+                </details>
+                <Show when=move || matches!(mat_filter.get(), MaterialFilter::Other(_))>
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Material name"
+                        aria-label="Material name"
+                        maxlength=MAX_OTHER_LEN
+                        node_ref=mat_other_input
+                        on:input=move |e| {
+                            if matches!(mat_filter.get_untracked(), MaterialFilter::Other(_)) {
+                                set_mat_filter.set(MaterialFilter::Other(event_target_value(&e)));
                             }
-                            on:change=move |e| {
-                                let v = event_target_value(&e);
-
-                                match v.as_str() {
-                                    "Any" => set_col_filter.set(ColorFilter::Any),
-                                    "Unspecified" => set_col_filter.set(ColorFilter::Unspecified),
-                                    "Other" => set_col_filter.set(ColorFilter::Other(String::new())),
-                                    _ => {
-                                        let chosen = KNOWN_COLORS.iter()
-                                            .find(|m| m.to_string() == v)
-                                            .cloned();
-                                        if let Some(m) = chosen {
-                                            set_col_filter.set(ColorFilter::Material(m));
-                                        } else {
-                                            set_col_filter.set(ColorFilter::Any);
-                                        }
-                                    }
+                        }
+                    />
+                    {move || match mat_filter.get() {
+                        MaterialFilter::Other(s) => other_length_hint(&s)
+                            .map(|h| view! { <small class="hint">{h}</small> }),
+                        _ => None,
+                    }}
+                </Show>
+            </div>
+            <div>
+                <label>"Color"</label>
+                <select
+                    class="input"
+                    prop:value=move || {
+                        // Tracked so the selection is re-applied once server-provided options render
+                        taxonomy.track();
+
+                        match col_filter.get() {
+                            ColorFilter::Any => "Any".to_string(),
+                            ColorFilter::Unspecified => "Unspecified".to_string(),
+                            ColorFilter::Other(_) => "Other".to_string(),
+                            ColorFilter::Material(c) => c.to_string(),
+                        }
+                    }
+                    on:change=move |e| {
+                        let v = event_target_value(&e);
+
+                        match v.as_str() {
+                            "Any" => set_col_filter.set(ColorFilter::Any),
+                            "Unspecified" => set_col_filter.set(ColorFilter::Unspecified),
+                            "Other" => {
+                                set_col_filter.set(ColorFilter::Other(String::new()));
+                                focus_when_shown(col_other_input);
+                            }
+                            _ => {
+                                let chosen = taxonomy.with_untracked(|t| {
+                                    t.colors.iter().find(|c| c.to_string() == v).cloned()
+                                });
+                                if let Some(m) = chosen {
+                                    set_col_filter.set(ColorFilter::Material(m));
+                                } else {
+                                    set_col_filter.set(ColorFilter::Any);
                                 }
                             }
-                        >
-                            <option value="Any">"Any"</option>
-                            { KNOWN_COLORS.iter()
+                        }
+                    }
+                >
+                    <option value="Any">"Any"</option>
+                    {move || {
+                        taxonomy.with(|t| {
+                            t.colors
+                                .iter()
                                 .map(|m| {
                                     let label = m.to_string();
                                     view! { <option value=label.clone()>{ label.clone() }</option> }
                                 })
                                 .collect_view()
+                        })
+                    }}
+                    <option value="Unspecified">"Unspecified"</option>
+                    <option value="Other">"Other…"</option>
+                </select>
+                <Show when=move || matches!(col_filter.get(), ColorFilter::Other(_))>
+                    <input
+                        class="input"
+                        type="text"
+                        placeholder="Color name"
+                        aria-label="Color name"
+                        maxlength=MAX_OTHER_LEN
+                        node_ref=col_other_input
+                        on:input=move |e| {
+                            if matches!(col_filter.get_untracked(), ColorFilter::Other(_)) {
+                                set_col_filter.set(ColorFilter::Other(event_target_value(&e)));
                             }
-                            <option value="Unspecified">"Unspecified"</option>
-                            <option value="Other">"Other…"</option>
-                        </select>
-                        <Show when=move || matches!(col_filter.get(), ColorFilter::Other(_))>
-                            <input
-                                class="input"
-                                type="text"
-                                placeholder="Color name"
-                                on:input=move |e| {
-                                    set_col_filter.update(|mf| {
-                                        if let ColorFilter::Other(s) = mf {
-                                            *s = event_target_value(&e);
-                                        }
-                                    });
-                                }
-                            />
-                        </Show>
-                    </div>
-                    <div>
-                        <label>"Diameter"</label>
-                        <select
-                            class="input"
-                            prop:value=move || match diam_filter.get() {
-                                DiameterFilter::Any => "Any".to_string(),
-                                DiameterFilter::D175 => "1.75".to_string(),
-                                DiameterFilter::D285 => "2.85".to_string(),
-                                DiameterFilter::Other(_) => "Other".to_string(),
+                        }
+                    />
+                    {move || match col_filter.get() {
+                        ColorFilter::Other(s) => other_length_hint(&s)
+                            .map(|h| view! { <small class="hint">{h}</small> }),
+                        _ => None,
+                    }}
+                </Show>
+            </div>
+            <div>
+                <label>"Diameter"</label>
+                <select
+                    class="input"
+                    prop:value=move || match diam_filter.get() {
+                        DiameterFilter::Any => "Any".to_string(),
+                        DiameterFilter::D175 => "1.75".to_string(),
+                        DiameterFilter::D285 => "2.85".to_string(),
+                        DiameterFilter::Other(_) => "Other".to_string(),
+                        DiameterFilter::Unspecified => "Unspecified".to_string(),
+                    }
+                    on:change=move |e| {
+                        match event_target_value(&e).as_str() {
+                            "Any" => set_diam_filter.set(DiameterFilter::Any),
+                            "1.75" => set_diam_filter.set(DiameterFilter::D175),
+                            "2.85" => set_diam_filter.set(DiameterFilter::D285),
+                            "Unspecified" => set_diam_filter.set(DiameterFilter::Unspecified),
+                            "Other" => {
+                                set_diam_filter.set(DiameterFilter::Other(String::new()));
+                                focus_when_shown(diam_other_input);
                             }
-                            on:change=move |e| {
-                                match event_target_value(&e).as_str() {
-                                    "Any" => set_diam_filter.set(DiameterFilter::Any),
-                                    "1.75" => set_diam_filter.set(DiameterFilter::D175),
-                                    "2.85" => set_diam_filter.set(DiameterFilter::D285),
-                                    "Other" => set_diam_filter.set(DiameterFilter::Other(String::new())),
-                                    _ => set_diam_filter.set(DiameterFilter::Any),
-                                }
+                            _ => set_diam_filter.set(DiameterFilter::Any),
+                        }
+                    }
+                >
+                    <option value="Any">"Any"</option>
+                    <option value="1.75">"1.75 mm"</option>
+                    <option value="2.85">"2.85 mm"</option>
+                    <option value="Other">"Other…"</option>
+                    <option value="Unspecified">"Unspecified"</option>
+                </select>
+                <Show when=move || matches!(diam_filter.get(), DiameterFilter::Other(_))>
+                    <input
+                        class="input"
+                        type="number"
+                        inputmode="numeric"
+                        placeholder="Millimeters (e.g. 1.75)"
+                        aria-label="Diameter in mm"
+                        node_ref=diam_other_input
+                        on:input=move |e| {
+                            if matches!(diam_filter.get_untracked(), DiameterFilter::Other(_)) {
+                                set_diam_filter.set(DiameterFilter::Other(event_target_value(&e)));
                             }
-                        >
-                            <option value="Any">"Any"</option>
-                            <option value="1.75">"1.75 mm"</option>
-                            <option value="2.85">"2.85 mm"</option>
-                            <option value="Other">"Other…"</option>
-                        </select>
-                        <Show when=move || matches!(diam_filter.get(), DiameterFilter::Other(_))>
-                            <input
-                                class="input"
-                                type="number"
-                                inputmode="numeric"
-                                placeholder="Millimeters (e.g. 1.75)"
-                                on:input=move |e| {
-                                    set_diam_filter.update(|df| {
-                                        if let DiameterFilter::Other(s) = df {
-                                            *s = event_target_value(&e);
-                                        }
-                                    });
-                                }
-                            />
-                        </Show>
-                    </div>
+                        }
+                    />
+                </Show>
+            </div>
 
-                    {/* Weight */}
-                    <div>
-                        <label>"Spool Weight"</label>
-                        <select
-                            class="input"
-                            prop:value=move || match weight_filter.get() {
-                                WeightFilter::Any => "Any".to_string(),
-                                WeightFilter::G500 => "500".to_string(),
-                                WeightFilter::G750 => "750".to_string(),
-                                WeightFilter::G1000 => "1000".to_string(),
-                                WeightFilter::G2000 => "2000".to_string(),
-                                WeightFilter::Other(_) => "Other".to_string(),
+            {/* Weight */}
+            <div>
+                <label>"Spool Weight"</label>
+                <select
+                    class="input"
+                    prop:value=move || match weight_filter.get() {
+                        WeightFilter::Any => "Any".to_string(),
+                        WeightFilter::G500 => "500".to_string(),
+                        WeightFilter::G750 => "750".to_string(),
+                        WeightFilter::G1000 => "1000".to_string(),
+                        WeightFilter::G2000 => "2000".to_string(),
+                        WeightFilter::Other(_) => "Other".to_string(),
+                    }
+                    on:change=move |e| {
+                        match event_target_value(&e).as_str() {
+                            "Any" => set_weight_filter.set(WeightFilter::Any),
+                            "500" => set_weight_filter.set(WeightFilter::G500),
+                            "750" => set_weight_filter.set(WeightFilter::G750),
+                            "1000" => set_weight_filter.set(WeightFilter::G1000),
+                            "2000" => set_weight_filter.set(WeightFilter::G2000),
+                            "Other" => {
+                                set_weight_filter.set(WeightFilter::Other(String::new()));
+                                focus_when_shown(weight_other_input);
                             }
-                            on:change=move |e| {
-                                match event_target_value(&e).as_str() {
-                                    "Any" => set_weight_filter.set(WeightFilter::Any),
-                                    "500" => set_weight_filter.set(WeightFilter::G500),
-                                    "750" => set_weight_filter.set(WeightFilter::G750),
-                                    "1000" => set_weight_filter.set(WeightFilter::G1000),
-                                    "2000" => set_weight_filter.set(WeightFilter::G2000),
-                                    "Other" => set_weight_filter.set(WeightFilter::Other(String::new())),
-                                    _ => set_weight_filter.set(WeightFilter::Any),
-                                }
+                            _ => set_weight_filter.set(WeightFilter::Any),
+                        }
+                    }
+                >
+                    <option value="Any">"Any"</option>
+                    <option value="500">"500 g"</option>
+                    <option value="750">"750 g"</option>
+                    <option value="1000">"1 kg"</option>
+                    <option value="2000">"2 kg"</option>
+                    <option value="Other">"Other…"</option>
+                </select>
+
+                <Show when=move || matches!(weight_filter.get(), WeightFilter::Other(_))>
+                    <input
+                        class="input"
+                        type="number"
+                        inputmode="numeric"
+                        placeholder="Kilograms (e.g. 1.2)"
+                        aria-label="Weight in kg"
+                        node_ref=weight_other_input
+                        on:input=move |e| {
+                            if matches!(weight_filter.get_untracked(), WeightFilter::Other(_)) {
+                                set_weight_filter.set(WeightFilter::Other(event_target_value(&e)));
                             }
-                        >
-                            <option value="Any">"Any"</option>
-                            <option value="500">"500 g"</option>
-                            <option value="750">"750 g"</option>
-                            <option value="1000">"1 kg"</option>
-                            <option value="2000">"2 kg"</option>
-                            <option value="Other">"Other…"</option>
-                        </select>
-
-                        <Show when=move || matches!(weight_filter.get(), WeightFilter::Other(_))>
-                            <input
-                                class="input"
-                                type="number"
-                                inputmode="numeric"
-                                placeholder="Kilograms (e.g. 1.2)"
-                                on:input=move |e| {
-                                    set_weight_filter.update(|wf| {
-                                        if let WeightFilter::Other(s) = wf {
-                                            *s = event_target_value(&e);
-                                        }
-                                    });
-                                }
-                            />
-                        </Show>
-                    </div>
-                </div>
-
-                <div class="options-row seek-row">
-                    <RangeSlider
-                        min_value=min_price_int
-                        set_min_value=set_min_price_int
-                        max_value=max_price_int
-                        set_max_value=set_max_price_int
-                        min_limit=0
-                        max_limit=100
-                        step=1
-                        gap=1
+                        }
                     />
-                    <div style="justify-content: center; align-items: center;">
-                        <button style="max-width: 400px" on:click=on_search>
-                            "Seek"
-                        </button>
-                    </div>
-                </div>
-            </section>
-
-            <section class="results">
-                {move || {
-                    if seeking.get() {
-                        view! { <div class="loading">"Seeking..."</div> }.into_any()
-                    } else if results.get().is_empty() {
-                        view! { <div class="empty">"No products match your filters."</div> }.into_any()
-                    } else {
-                        view! { <ProductTable
-                            products=results
-                            page=page
-                            total_pages=total_pages
-                            set_page=set_page
-                            total_results=total_results
-                            sortby=sortby
-                            set_sortby=set_sortby
-                        /> }.into_any()
-                    }
-                }}
-            </section>
+                </Show>
+            </div>
         </div>
     }
 }
@@ -686,22 +2000,59 @@ pub fn ProductSearch() -> impl IntoView {
 #[component]
 fn ProductTable(
     products: ReadSignal<Vec<Product>>,
+    /// Used by admins' inline edits to replace a row with the saved product
+    set_products: WriteSignal<Vec<Product>>,
     set_page: WriteSignal<u32>,
     page: ReadSignal<u32>,
     total_pages: ReadSignal<u32>,
     total_results: ReadSignal<u32>,
-    sortby: ReadSignal<SortBy>,
-    set_sortby: WriteSignal<SortBy>,
+    /// Page size the results were requested with
+    per_page: u32,
+    aggregates: ReadSignal<Option<SearchAggregates>>,
+    /// The currency filter, which every result is priced in
+    #[prop(into)]
+    currency: Signal<Currency>,
+    #[prop(into)] sort_keys: Signal<Vec<SortKey>>,
+    #[prop(into)] set_sort_keys: SignalSetter<Vec<SortKey>>,
 ) -> impl IntoView {
-    let p = page.get_untracked();
     let total = total_results.get_untracked();
+    let (start, end) = result_range(page.get_untracked(), per_page, total);
+
+    let is_admin = use_is_admin();
+    let outlier_threshold = Memo::new(move |_| low_price_outlier_threshold(&products.get()));
+    let best_value =
+        Memo::new(move |_| products.with(|p| best_value_uuid(p, outlier_threshold.get())));
+
+    let prefs = use_preferences();
+    let compact = move || prefs.with(|p| p.density == Density::Compact);
+    let viewport_width = use_viewport_width();
+    // Wide cells are hidden by CSS on narrow screens anyway, so don't build them
+    let hide_wide = move || compact() || viewport_width.get() <= NARROW_MAX_WIDTH;
+    let shows = move |column| prefs.with(|p| p.shows_column(column));
+    // Name takes four shares of the width, each other visible column one
+    let grid_columns = move || {
+        let visible = prefs.with(|p| TableColumn::iter().filter(|c| p.shows_column(*c)).count());
+        format!("--product-grid-wide: 4fr{}", " 1fr".repeat(visible))
+    };
 
-    let start = if total == 0 {
-        0
-    } else {
-        (p.saturating_sub(1)) * MAX_PAGE_SIZE + 1
+    let (copy_status, set_copy_status) = signal::<Option<String>>(None);
+    // Shown when the clipboard can't be written, so the table can be copied by hand
+    let copy_fallback = RwSignal::new(false);
+    let copy_text = StoredValue::new(String::new());
+
+    let on_copy = move |_| {
+        let text = results_as_markdown(&products.get_untracked());
+        let rows = products.with_untracked(|p| p.len());
+        copy_text.set_value(text.clone());
+
+        spawn_local(async move {
+            if clipboard::copy_to_clipboard(&text).await {
+                set_copy_status.set(Some(format!("Copied {rows} rows")));
+            } else {
+                copy_fallback.set(true);
+            }
+        });
     };
-    let end = (p * MAX_PAGE_SIZE).min(total);
 
     let summary = if total_pages.get_untracked() == 1 {
         format!("{total} results")
@@ -709,106 +2060,656 @@ fn ProductTable(
         format!("{start}-{end} of {total} results")
     };
 
+    let stats = move || {
+        prefs.with(|p| p.show_stats).then(|| {
+            let currency = currency.get_untracked();
+            let full_set = aggregates.get_untracked().unwrap_or_default();
+            // $/kg falls back to this page's figures; price is only shown when the backend sends it
+            let price_per_kg = full_set
+                .price_per_kg
+                .map(|a| (a, true))
+                .or_else(|| {
+                    PriceStats::price_per_kg_of(&products.get_untracked()).map(|s| (s, false))
+                })
+                .map(|(stats, full_set)| {
+                    view! { <StatsStrip
+                        label=TableColumn::PricePerKg.label(currency)
+                        stats=stats
+                        full_set=full_set
+                        total=total
+                        currency=currency
+                    /> }
+                });
+            let price = full_set.price.map(|stats| {
+                view! { <StatsStrip
+                    label=TableColumn::Price.label(currency)
+                    stats=stats
+                    full_set=true
+                    total=total
+                    currency=currency
+                /> }
+            });
+
+            view! {
+                {price_per_kg}
+                {price}
+            }
+        })
+    };
+
     view! {
         <Pagination page=page total_pages=total_pages set_page=set_page />
         <div style="text-align: right;">
+            <button
+                class="link-button"
+                style="margin-right: 12px;"
+                on:click=move |_| {
+                    prefs.update(|p| {
+                        p.density = if p.density == Density::Compact {
+                            Density::Comfortable
+                        } else {
+                            Density::Compact
+                        };
+                    })
+                }
+            >
+                {move || if compact() { "Comfortable view" } else { "Compact view" }}
+            </button>
+            <ColumnsMenu currency=currency />
+            <button class="link-button" style="margin-right: 12px;" on:click=on_copy>
+                {move || copy_status.get().unwrap_or_else(|| "Copy as table".to_string())}
+            </button>
             {summary.clone()}
         </div>
-        <div class="product-grid">
+        {stats}
+        <small class="hint" style="text-align: right;">"Shift-click a column to add it as a secondary sort"</small>
+        <div class="product-grid" class:compact-forced=compact style=grid_columns>
             <div class="product-grid-header">
                 <div class="product-grid-header-row">
-                    <div class="product-grid-header-cell">"Name"</div>
-                    <div class="product-grid-header-cell wide-col">
-                        <button
-                            disabled={move || matches!(sortby.get(), SortBy::Price)}
-                            on:click=move |_| {
-                                set_sortby.set(SortBy::Price);
-                            }>
-                            "Price"
-                        </button>
-                    </div>
-                    <div class="product-grid-header-cell wide-col">
-                        <button
-                            disabled={move || matches!(sortby.get(), SortBy::PricePerKg)}
-                            on:click=move |_| {
-                                set_sortby.set(SortBy::PricePerKg);
-                            }>
-                            "$ / kg"
-                        </button>
+                    <div
+                        class="product-grid-header-cell"
+                        role="columnheader"
+                        aria-sort=move || sort_keys.with(|k| aria_sort(k, SortBy::Name))
+                    >
+                        <SortButton by=SortBy::Name label="Name" sort_keys=sort_keys set_sort_keys=set_sort_keys />
                     </div>
+                    {move || {
+                        (!hide_wide()).then(|| {
+                            // Same columns, in the same order, as ProductRow's wide cells
+                            TableColumn::iter()
+                                .filter(|c| shows(*c))
+                                .map(|column| {
+                                    let sort_by = match column {
+                                        TableColumn::Price => Some(SortBy::Price),
+                                        TableColumn::PricePerKg => Some(SortBy::PricePerKg),
+                                        TableColumn::Material => Some(SortBy::Material),
+                                        TableColumn::Weight => Some(SortBy::Weight),
+                                        _ => None,
+                                    };
+                                    let label = column.label(currency.get());
+                                    let content = match sort_by {
+                                        Some(by) => view! {
+                                            <SortButton by=by label=label sort_keys=sort_keys set_sort_keys=set_sort_keys />
+                                        }
+                                        .into_any(),
+                                        None => label.into_any(),
+                                    };
+
+                                    view! {
+                                        <div
+                                            class="product-grid-header-cell wide-col"
+                                            role="columnheader"
+                                            aria-sort=move || sort_by.map(|by| sort_keys.with(|k| aria_sort(k, by)))
+                                        >
+                                            {content}
+                                        </div>
+                                    }
+                                })
+                                .collect_view()
+                        })
+                    }}
                     <div class="product-grid-header-cell compact-col">
-                        <button
-                            style="margin-bottom: 8px;"
-                            disabled={move || matches!(sortby.get(), SortBy::Price)}
-                            on:click=move |_| {
-                                set_sortby.set(SortBy::Price);
-                            }>
-                            "$"
-                        </button>
-                        <button
-                            disabled={move || matches!(sortby.get(), SortBy::PricePerKg)}
-                            on:click=move |_| {
-                                set_sortby.set(SortBy::PricePerKg);
-                            }>
-                            "$ / kg"
-                        </button>
+                        <div style="margin-bottom: 8px; width: 100%;">
+                            <SortButton
+                                by=SortBy::Price
+                                label=Signal::derive(move || currency.get().symbol().to_string())
+                                sort_keys=sort_keys
+                                set_sort_keys=set_sort_keys
+                            />
+                        </div>
+                        <SortButton
+                            by=SortBy::PricePerKg
+                            label=Signal::derive(move || TableColumn::PricePerKg.label(currency.get()))
+                            sort_keys=sort_keys
+                            set_sort_keys=set_sort_keys
+                        />
                     </div>
-                    <div class="product-grid-header-cell wide-col">"Material"</div>
-                    <div class="product-grid-header-cell wide-col">"Color"</div>
-                    <div class="product-grid-header-cell wide-col">"Diameter"</div>
-                    <div class="product-grid-header-cell wide-col">"Weight"</div>
                     <div class="product-grid-header-cell compact-col" style="align-items: start;">"Specs"</div>
-                    <div class="product-grid-header-cell wide-col">"Retailer"</div>
                 </div>
-            </div>
-            <div class="product-grid-body">
-                <For
-                    each=move || products.get()
-                    key=|p| p.uuid.clone()
-                    children=move |p: Product| view! { <ProductRow product=p /> }
-                />
-            </div>
-        </div>
-        <div style="text-align: center;">
-            {summary}
-        </div>
-        <Pagination page=page total_pages=total_pages set_page=set_page />
-    }
-}
+            </div>
+            <div class="product-grid-body">
+                <For
+                    each=move || products.get()
+                    // The price is part of the key so a row re-renders after an inline price edit
+                    key=|p| (p.uuid.clone(), p.price.0)
+                    children=move |p: Product| {
+                        let unusually_low = outlier_threshold.get_untracked().is_some_and(|t| {
+                            p.displayable_price_per_kg()
+                                .is_some_and(|ppk| ppk.0 > 0 && (ppk.0 as f32) < t)
+                        });
+                        view! {
+                            <ProductRow
+                                product=p
+                                unusually_low=unusually_low
+                                best_value=best_value
+                                is_admin=is_admin
+                                set_products=set_products
+                            />
+                        }
+                    }
+                />
+            </div>
+        </div>
+        <div style="text-align: center;">
+            {summary}
+        </div>
+        <Modal open=copy_fallback title="Copy as table">
+            <p class="hint">"The clipboard isn't available here. Select the text below and copy it."</p>
+            <textarea class="input" rows="12" readonly prop:value=move || copy_text.get_value()></textarea>
+        </Modal>
+        <Pagination page=page total_pages=total_pages set_page=set_page />
+    }
+}
+
+/// How long ago the price was checked, amber once it's stale. Renders nothing when unknown.
+#[component]
+pub fn FreshnessBadge(product: Product) -> impl IntoView {
+    let stale = product.price_is_stale();
+
+    product.price_age_secs().map(|age| {
+        view! {
+            <small
+                class="freshness"
+                class:stale=stale
+                title=product.price_updated_at.clone().unwrap_or_default()
+            >
+                {format!("updated {}", humanize_age(age))}
+            </small>
+        }
+    })
+}
+
+#[derive(Serialize)]
+struct PricePatch {
+    price: Cents,
+}
+
+/// A price admins can click to change in place: Enter saves just the price, Escape cancels
+#[component]
+fn EditablePrice(product: Product, set_products: WriteSignal<Vec<Product>>) -> impl IntoView {
+    let toasts = use_toasts();
+    let (editing, set_editing) = signal(false);
+    let (saving, set_saving) = signal(false);
+    let (draft, set_draft) = signal(product.price.to_dollars_str());
+    let input = NodeRef::<html::Input>::new();
+    let uuid = StoredValue::new(product.uuid.clone());
+    let price = product.price;
+    let currency = product.currency;
+    // Filled in once `save` exists, so a toast for a network failure can rerun it
+    let retry_save = StoredValue::new(None::<Callback<()>>);
+
+    let start_editing = move |_| {
+        set_editing.set(true);
+        focus_when_shown(input);
+    };
+
+    let save = move || {
+        let Some(new_price) = Cents::from_dollars_str(&draft.get_untracked()).filter(|p| p.0 > 0)
+        else {
+            toasts.error("Enter a price greater than zero, e.g. 19.99");
+            return;
+        };
+
+        if new_price == price {
+            set_editing.set(false);
+            return;
+        }
+
+        set_saving.set(true);
+
+        spawn_local(async move {
+            let path = format!("products/{}", uuid.get_value());
+            let res = request_json::<PricePatch, Product>(
+                &path,
+                Auth::Authorized,
+                Method::PATCH,
+                Some(&PricePatch { price: new_price }),
+            )
+            .await;
+
+            match res {
+                Ok(saved) => {
+                    set_saving.set(false);
+                    set_editing.set(false);
+                    toasts.success(format!("Price of {} set to {}", saved.name, saved.price));
+                    set_products.update(|products| {
+                        if let Some(p) = products.iter_mut().find(|p| p.uuid == saved.uuid) {
+                            *p = saved;
+                        }
+                    });
+                }
+                Err(e) => {
+                    set_saving.set(false);
+                    let message =
+                        format!("Couldn't update the price: ({}) {}", e.status, e.message);
+
+                    match retry_save.get_value() {
+                        Some(retry) if e.is_network_error() => {
+                            toasts.error_with_retry(message, retry)
+                        }
+                        _ => toasts.error(message),
+                    }
+                }
+            }
+        });
+    };
+    retry_save.set_value(Some(Callback::new(move |()| save())));
+
+    view! {
+        <Show
+            when=move || editing.get()
+            fallback=move || {
+                view! {
+                    <button class="link-button" title="Click to edit the price" on:click=start_editing>
+                        {price.format_in(currency)}
+                    </button>
+                }
+            }
+        >
+            <input
+                class="input inline-price"
+                type="text"
+                inputmode="decimal"
+                aria-label=format!("Price in {currency}")
+                node_ref=input
+                disabled=move || saving.get()
+                prop:value=move || draft.get()
+                on:input=move |e| set_draft.set(event_target_value(&e))
+                on:keydown=move |e| match e.key().as_str() {
+                    "Enter" => {
+                        e.prevent_default();
+                        save();
+                    }
+                    "Escape" => {
+                        set_draft.set(price.to_dollars_str());
+                        set_editing.set(false);
+                    }
+                    _ => {}
+                }
+            />
+        </Show>
+    }
+}
+
+/// Copies a product's UUID for admins. Shows the UUID to copy by hand if the clipboard is
+/// unavailable.
+#[component]
+fn CopyUuidButton(uuid: String) -> impl IntoView {
+    let uuid = StoredValue::new(uuid);
+    let (status, set_status) = signal::<Option<bool>>(None);
+    let reset = StoredValue::new(None::<TimeoutHandle>);
+
+    on_cleanup(move || {
+        if let Some(handle) = reset.get_value() {
+            handle.clear();
+        }
+    });
+
+    let on_click = move |_| {
+        spawn_local(async move {
+            let copied = clipboard::copy_to_clipboard(&uuid.get_value()).await;
+            set_status.set(Some(copied));
+
+            if let Some(handle) = reset.get_value() {
+                handle.clear();
+            }
+            let handle = set_timeout_with_handle(
+                move || set_status.set(None),
+                Duration::from_millis(COPY_STATUS_MS),
+            )
+            .ok();
+            reset.set_value(handle);
+        });
+    };
+
+    view! {
+        <small class="hint">
+            <button class="link-button" on:click=on_click>
+                {move || match status.get() {
+                    Some(true) => "UUID copied".to_string(),
+                    _ => "Copy UUID".to_string(),
+                }}
+            </button>
+            {move || {
+                (status.get() == Some(false)).then(|| {
+                    view! {
+                        " "
+                        <code class="selectable">{uuid.get_value()}</code>
+                    }
+                })
+            }}
+        </small>
+    }
+}
+
+/// Checkboxes for showing and hiding the results columns
+#[component]
+fn ColumnsMenu(#[prop(into)] currency: Signal<Currency>) -> impl IntoView {
+    let prefs = use_preferences();
+
+    view! {
+        <details class="columns-menu">
+            <summary class="link-button">"Columns"</summary>
+            <div class="columns-menu-list">
+                {TableColumn::iter()
+                    .map(|column| {
+                        view! {
+                            <label class="checkbox-label">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || prefs.with(|p| p.shows_column(column))
+                                    // The last price column can't be turned off
+                                    disabled=move || {
+                                        prefs.with(|p| p.shows_column(column) && !p.can_hide_column(column))
+                                    }
+                                    on:change=move |e| {
+                                        let shown = event_target_checked(&e);
+                                        prefs.update(|p| p.set_column_shown(column, shown));
+                                    }
+                                />
+                                {move || column.label(currency.get())}
+                            </label>
+                        }
+                    })
+                    .collect_view()}
+            </div>
+        </details>
+    }
+}
+
+/// A column header that sorts by `by`; shift-click adds it as a tie-breaker instead
+#[component]
+fn SortButton(
+    by: SortBy,
+    #[prop(into)] label: Signal<String>,
+    #[prop(into)] sort_keys: Signal<Vec<SortKey>>,
+    #[prop(into)] set_sort_keys: SignalSetter<Vec<SortKey>>,
+) -> impl IntoView {
+    let position = move || sort_keys.with(|keys| keys.iter().position(|k| k.by == by));
+
+    let accessible_name = move || {
+        let direction = sort_keys.with(|keys| {
+            keys.iter()
+                .find(|k| k.by == by)
+                .map(|k| match k.direction {
+                    SortDirection::Asc => ", ascending",
+                    SortDirection::Desc => ", descending",
+                })
+                .unwrap_or_default()
+        });
+
+        format!("Sort by {}{direction}", by.spoken_name())
+    };
+
+    let indicator = move || {
+        sort_keys.with(|keys| {
+            let (i, key) = keys.iter().enumerate().find(|(_, k)| k.by == by)?;
+            let arrow = match key.direction {
+                SortDirection::Asc => "↑",
+                SortDirection::Desc => "↓",
+            };
+
+            Some(if keys.len() > 1 {
+                format!(" {arrow}{}", i + 1)
+            } else {
+                format!(" {arrow}")
+            })
+        })
+    };
+
+    view! {
+        <button
+            aria-label=accessible_name
+            class:sort-active=move || position().is_some()
+            on:click=move |e| {
+                let mut keys = sort_keys.get_untracked();
+                apply_sort_click(&mut keys, by, e.shift_key());
+                set_sort_keys.set(keys);
+            }
+        >
+            {label}
+            {indicator}
+        </button>
+    }
+}
+
+#[component]
+fn ProductRow(
+    product: Product,
+    unusually_low: bool,
+    /// UUID of the row to badge as the best value on the page
+    best_value: Memo<Option<String>>,
+    #[prop(into)] is_admin: Signal<bool>,
+    set_products: WriteSignal<Vec<Product>>,
+) -> impl IntoView {
+    let is_best_value = {
+        let uuid = product.uuid.clone();
+        move || best_value.with(|b| b.as_deref() == Some(uuid.as_str()))
+    };
+
+    let prefs = use_preferences();
+    let currency = product.currency;
+    let money = move |c: Cents| {
+        if prefs.with(|p| p.compact_prices) {
+            c.format_compact(currency)
+        } else {
+            c.format_in(currency)
+        }
+    };
+
+    let sale_note = product
+        .discount()
+        .zip(product.list_price)
+        .map(|(discount, list_price)| {
+            view! {
+                <div>
+                    <s class="list-price">{move || money(list_price)}</s>
+                    " "
+                    <span class="badge">{discount.to_string()}</span>
+                </div>
+            }
+        });
+
+    let low_note = move || {
+        unusually_low.then(|| {
+            view! {
+                <div
+                    class="price-note"
+                    title="Much cheaper per kg than the other results. It may be a small spool, a sample, or a listing error."
+                >
+                    "unusually low — check spool size"
+                </div>
+            }
+        })
+    };
+
+    let displayable_ppk = product.displayable_price_per_kg();
+    let price_per_kg = move |suffix: &'static str| match displayable_ppk {
+        Some(ppk) => view! { <span>{move || format!("{}{suffix}", money(ppk))}</span> }.into_any(),
+        None => view! { <span title="Weight unknown">"—"</span> }.into_any(),
+    };
+    // The compact column can trade the cents for less noise; the wide one always keeps them
+    let compact_price_per_kg = move || match displayable_ppk {
+        Some(ppk) if prefs.with(|p| p.round_compact_price_per_kg) => view! {
+            <span title=format!("{}/kg", ppk.format_in(currency))>
+                {format!("{}/kg", ppk.format_whole_dollars(currency))}
+            </span>
+        }
+        .into_any(),
+        _ => price_per_kg("/kg"),
+    };
+    // Fractions of a cent, so this keeps more digits than Cents can
+    let dollars_per_meter = product.dollars_per_meter();
+    let price_per_meter = move || match dollars_per_meter {
+        Some(d) => view! { <span>{format!("{}{d:.3}/m", currency.symbol())}</span> }.into_any(),
+        None => view! { <span title="Material density, diameter, or weight unknown">"—"</span> }
+            .into_any(),
+    };
+    let net_ppk = product.net_price_per_kg();
+    let net_note = move |suffix: &'static str| {
+        net_ppk.map(|ppk| {
+            view! {
+                <div class="price-note" title="Listed weight includes the spool; this excludes the empty spool">
+                    {move || format!("{}{suffix} net", money(ppk))}
+                </div>
+            }
+        })
+    };
 
-#[component]
-fn ProductRow(product: Product) -> impl IntoView {
-    let url = product.url.clone();
+    let compact = move || prefs.with(|p| p.density == Density::Compact);
+    let viewport_width = use_viewport_width();
+    let hide_wide = move || compact() || viewport_width.get() <= NARROW_MAX_WIDTH;
+
+    let ships_to = (!product.ships_to.is_empty()).then(|| {
+        let codes: Vec<&str> = product.ships_to.iter().map(|r| r.code()).collect();
+        view! { <small class="hint">{format!("Ships to {}", codes.join(", "))}</small> }
+    });
+
+    let wide_cells = {
+        let product = product.clone();
+        let sale_note = sale_note.clone();
+
+        move || {
+            (!hide_wide()).then(|| {
+                TableColumn::iter()
+                    .filter(|c| prefs.with(|p| p.shows_column(*c)))
+                    .map(|column| match column {
+                        TableColumn::Price => view! {
+                            <div class="product-grid-cell wide-col">
+                                {if is_admin.get() {
+                                    view! { <EditablePrice product=product.clone() set_products=set_products /> }
+                                        .into_any()
+                                } else {
+                                    let price = product.price;
+                                    (move || money(price)).into_any()
+                                }}
+                                {sale_note.clone()}
+                            </div>
+                        }
+                        .into_any(),
+                        TableColumn::PricePerKg => view! {
+                            <div class="product-grid-cell wide-col">
+                                {price_per_kg("")}
+                                {low_note()}
+                                {net_note("")}
+                            </div>
+                        }
+                        .into_any(),
+                        TableColumn::PricePerMeter => view! {
+                            <div class="product-grid-cell wide-col">{price_per_meter()}</div>
+                        }
+                        .into_any(),
+                        TableColumn::Material => view! {
+                            <div class="product-grid-cell wide-col">{product.material.to_string()}</div>
+                        }
+                        .into_any(),
+                        TableColumn::Color => view! {
+                            <div class="product-grid-cell wide-col">
+                                <ColorChip color=product.color.clone() />
+                                {product.color.to_string()}
+                            </div>
+                        }
+                        .into_any(),
+                        TableColumn::Diameter => view! {
+                            <div class="product-grid-cell wide-col">{product.diameter.to_string()}</div>
+                        }
+                        .into_any(),
+                        TableColumn::Weight => view! {
+                            <div class="product-grid-cell wide-col">{product.weight_label()}</div>
+                        }
+                        .into_any(),
+                        TableColumn::Retailer => view! {
+                            <div class="product-grid-cell wide-col">
+                                {product.retailer.to_string()}
+                                {ships_to.clone()}
+                                {(product.retailer == Retailer::Amazon)
+                                    .then(|| view! { <div>"(#ad)"</div> })}
+                            </div>
+                        }
+                        .into_any(),
+                    })
+                    .collect_view()
+            })
+        }
+    };
+
+    let name_title = match product.description_preview() {
+        Some(preview) => format!("{}\n{preview}", product.name),
+        None => product.name.clone(),
+    };
 
     view! {
-        <a href={url.clone()} target="_blank" class="product-grid-row" data-product-id={product.uuid.clone()}>
-            <div class="product-grid-cell">{product.name.clone()}</div>
-            <div class="product-grid-cell wide-col">{product.price.to_string()}</div>
-            <div class="product-grid-cell wide-col">{product.price_per_kg.to_string()}</div>
+        // The name links to the product page and stretches over the row, so the whole row is
+        // clickable while its buttons and inputs stay outside the link
+        <div class="product-grid-row" data-product-id={product.uuid.clone()}>
+            <div class="product-grid-cell">
+                // Truncated with an ellipsis by CSS; the full name stays in the text and the tooltip
+                <span class="product-name" title=name_title>
+                    <FavoriteButton uuid=product.uuid.clone() />
+                    <a href=product.canonical_path() class="stretched-link">{product.name.clone()}</a>
+                </span>
+                {(product.in_stock == Some(false))
+                    .then(|| view! { <small class="hint">"Out of stock"</small> })}
+                <FreshnessBadge product=product.clone() />
+                <BuyButton url=product.url.clone() retailer=product.retailer.clone() />
+                {
+                    let uuid = product.uuid.clone();
+                    move || is_admin.get().then(|| view! { <CopyUuidButton uuid=uuid.clone() /> })
+                }
+                <Show when=is_best_value.clone()>
+                    <span class="badge best-value">"Best value on this page"</span>
+                </Show>
+                {(!product.tags.is_empty()).then(|| {
+                    view! {
+                        <div class="tag-chips">
+                            {product
+                                .tags
+                                .iter()
+                                .map(|t| view! { <span class="tag">{t.clone()}</span> })
+                                .collect_view()}
+                        </div>
+                    }
+                })}
+            </div>
+            {wide_cells}
 
             <div class="product-grid-cell compact-col">
-                {product.price.to_string()}
+                {move || money(product.price)}
+                {sale_note}
                 <br />
                 <br />
-                {product.price_per_kg.to_string()}"/kg"
-            </div>
-
-            <div class="product-grid-cell wide-col">{product.material.to_string()}</div>
-
-            <div class="product-grid-cell wide-col" style=format!("color: {}", product.color.hex())>
-                {product.color.to_string()}
+                {compact_price_per_kg}
+                {low_note()}
+                {net_note("/kg")}
             </div>
 
-            <div class="product-grid-cell wide-col">{product.diameter.to_string()}</div>
-            <div class="product-grid-cell wide-col">{product.weight.to_string()}</div>
-
             <div class="product-grid-cell compact-col compact-specs">
                 <div>"Mat: "{product.material.to_string()}</div>
-                <div style=format!("color: {}", product.color.hex())>
-                    "Col: "{product.color.to_string()}
+                <div>
+                    "Col: " <ColorChip color=product.color.clone() /> {product.color.to_string()}
                 </div>
                 <div>"Dia: "{product.diameter.to_string()}</div>
-                <div>"Wt: "{product.weight.to_string()}</div>
+                <div>"Wt: "{product.weight_label()}</div>
                 <div>
                     {product.retailer.to_string()}
                     {if product.retailer == Retailer::Amazon {
@@ -819,21 +2720,179 @@ fn ProductRow(product: Product) -> impl IntoView {
                     }}
                 </div>
             </div>
+        </div>
+    }
+}
 
-            <div class="product-grid-cell wide-col">
-                {product.retailer.to_string()}
-                {if product.retailer == Retailer::Amazon {
-                    view! { <div>"(#ad)"</div> }.into_any()
-                } else {
-                    let _: () = view! { <></> };
-                    ().into_any()
-                }}
-            </div>
+/// Opens the listing at the retailer
+#[component]
+fn BuyButton(url: String, retailer: Retailer) -> impl IntoView {
+    let prefs = use_preferences();
+    let target = move || {
+        if prefs.with(|p| p.open_links_in_new_tab) {
+            "_blank"
+        } else {
+            "_self"
+        }
+    };
+
+    view! {
+        <a class="buy-button" href=url target=target rel="noopener">
+            {format!("Buy at {retailer}")}
+            {(retailer == Retailer::Amazon).then_some(" (#ad)")}
         </a>
     }
 }
 
-async fn search_products(request: &ProductSearchRequest) -> ProductSearchResponse {
+/// The results as an aligned Markdown table, readable as plain text and rendered as a table where Markdown is supported
+fn results_as_markdown(products: &[Product]) -> String {
+    // Amounts are plain decimals, without "$", so spreadsheets read them as numbers
+    let header = ["Name", "Price", "Per kg", "Currency", "Material", "Color"].map(String::from);
+    let rows: Vec<[String; 6]> = products
+        .iter()
+        .map(|p| {
+            [
+                p.name.replace('|', "\\|"),
+                p.price.to_plain_decimal(),
+                p.displayable_price_per_kg()
+                    .map_or_else(String::new, Cents::to_plain_decimal),
+                p.currency.to_string(),
+                p.material.to_string(),
+                p.color.to_string(),
+            ]
+        })
+        .collect();
+
+    let mut widths = header.clone().map(|h| h.chars().count());
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &[String; 6]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(widths)
+            .map(|(c, w)| format!("{c}{}", " ".repeat(w - c.chars().count())))
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+
+    let divider: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+
+    let mut lines = vec![line(&header), format!("| {} |", divider.join(" | "))];
+    lines.extend(rows.iter().map(line));
+    lines.join("\n")
+}
+
+/// The product with the lowest $/kg, skipping unknown weights and the outliers below
+/// `outlier_threshold`, which are more likely listing errors than bargains
+fn best_value_uuid(products: &[Product], outlier_threshold: Option<f32>) -> Option<String> {
+    products
+        .iter()
+        .filter_map(|p| p.displayable_price_per_kg().map(|ppk| (p, ppk.0)))
+        .filter(|(_, ppk)| *ppk > 0 && outlier_threshold.is_none_or(|t| *ppk as f32 >= t))
+        .min_by_key(|(_, ppk)| *ppk)
+        .map(|(p, _)| p.uuid.clone())
+}
+
+/// The $/kg (in cents) below which a product is an outlier among `products`, if there are enough to tell
+fn low_price_outlier_threshold(products: &[Product]) -> Option<f32> {
+    let mut values: Vec<f32> = products
+        .iter()
+        .filter_map(|p| p.displayable_price_per_kg())
+        .filter(|ppk| ppk.0 > 0)
+        .map(|ppk| ppk.0 as f32)
+        .collect();
+
+    if values.len() < MIN_OUTLIER_SAMPLES {
+        return None;
+    }
+
+    values.sort_by(f32::total_cmp);
+
+    let percentile = |p: f32| {
+        let rank = p * (values.len() - 1) as f32;
+        let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+        values[lo] + (values[hi] - values[lo]) * (rank - lo as f32)
+    };
+
+    let iqr = percentile(0.75) - percentile(0.25);
+    Some(percentile(0.5) - OUTLIER_IQR_K * iqr)
+}
+
+/// The last page, when `page` is past it; `None` when `page` is within `total_pages`. An empty
+/// result set still has a page 1.
+fn page_past_end(page: u32, total_pages: u64) -> Option<u32> {
+    let last_page = total_pages.clamp(1, u32::MAX as u64) as u32;
+    (page > last_page).then_some(last_page)
+}
+
+/// Decides whether each URL write adds a history entry or replaces the current one
+struct UrlHistory {
+    /// Set by a search the user asked for, and used up by the next write
+    push_next: bool,
+    /// The page in the URL as last written
+    page: u32,
+}
+
+impl UrlHistory {
+    fn new(page: u32) -> Self {
+        UrlHistory {
+            push_next: false,
+            page,
+        }
+    }
+
+    /// Makes the next write add a history entry
+    fn push_next(&mut self) {
+        self.push_next = true;
+    }
+
+    /// Moves to `page` without that counting as a page change, for a correction the user didn't
+    /// ask for. An explicit search still waiting to be written keeps its entry.
+    fn correct_page(&mut self, page: u32) {
+        self.page = page;
+    }
+
+    /// Whether writing the URL for `page` adds a history entry: after an explicit search, or
+    /// when moving to another page
+    fn is_push(&mut self, page: u32) -> bool {
+        let push = std::mem::take(&mut self.push_next) || page != self.page;
+        self.page = page;
+        push
+    }
+}
+
+/// The page a search for `current` should fetch. Whatever triggered it, a search with different
+/// filters than the shown results starts from the first page; page 5 of the old results says
+/// nothing about the new ones.
+fn search_page(searched: Option<&FilterState>, current: &FilterState, page: u32) -> u32 {
+    if searched.is_some_and(|s| s != current) {
+        1
+    } else {
+        page
+    }
+}
+
+/// 1-based positions of the first and last results on `page`, or `(0, 0)` when there are none
+fn result_range(page: u32, per_page: u32, total: u32) -> (u32, u32) {
+    if total == 0 {
+        return (0, 0);
+    }
+
+    let start = page
+        .saturating_sub(1)
+        .saturating_mul(per_page)
+        .saturating_add(1);
+    (start.min(total), page.saturating_mul(per_page).min(total))
+}
+
+/// Like [`search_products`], but reports failures instead of treating them as no results
+pub async fn try_search_products(
+    request: &ProductSearchRequest,
+) -> Result<ProductSearchResponse, ErrorResponse> {
     request_json::<ProductSearchRequest, ProductSearchResponse>(
         "products/search",
         Auth::Unauthorized,
@@ -841,11 +2900,18 @@ async fn search_products(request: &ProductSearchRequest) -> ProductSearchRespons
         Some(request),
     )
     .await
-    .unwrap_or(ProductSearchResponse {
-        items: vec![],
-        total: 0,
-        total_pages: 1,
-    })
+}
+
+pub async fn search_products(request: &ProductSearchRequest) -> ProductSearchResponse {
+    try_search_products(request)
+        .await
+        .unwrap_or(ProductSearchResponse {
+            items: vec![],
+            total: 0,
+            total_pages: 1,
+            aggregates: None,
+            applied_filters: None,
+        })
 }
 
 #[component]
@@ -856,9 +2922,26 @@ pub fn Pagination(
 ) -> impl IntoView {
     let go = move |n: u32| set_page.set(n.clamp(1, total_pages.get()));
     let pages = move || (1..=total_pages.get()).collect::<Vec<u32>>();
+    let jump_input = NodeRef::<html::Input>::new();
+
+    let on_jump = move |e: ev::SubmitEvent| {
+        e.prevent_default();
+
+        let Some(input) = jump_input.get() else {
+            return;
+        };
+
+        match parse_url_number(input.value().trim()) {
+            Some(n) => go(n.min(u32::MAX as u64) as u32),
+            None => input.set_value(&page.get_untracked().to_string()),
+        }
+    };
 
     view! {
-        <nav style="display: flex; flex-direction: row; justify-content: center; flex-wrap: wrap">
+        <nav
+            aria-label="Pagination"
+            style="display: flex; flex-direction: row; justify-content: center; align-items: center; flex-wrap: wrap"
+        >
             <For
                 each=pages
                 key=|n| *n
@@ -875,6 +2958,20 @@ pub fn Pagination(
                     }
                 }
             />
+            <form class="page-jump" on:submit=on_jump>
+                <label for="page-jump-input">"Go to page"</label>
+                <input
+                    id="page-jump-input"
+                    node_ref=jump_input
+                    class="input"
+                    type="number"
+                    inputmode="numeric"
+                    min="1"
+                    max=move || total_pages.get()
+                    prop:value=move || page.get().to_string()
+                />
+                <button type="submit">"Go"</button>
+            </form>
         </nav>
     }
 }
@@ -884,14 +2981,163 @@ pub struct ProductSearchResponse {
     pub items: Vec<Product>,
     pub total: u64,
     pub total_pages: u64,
+    /// Statistics over every matching product, when requested and the backend provides them
+    #[serde(default)]
+    pub aggregates: Option<SearchAggregates>,
+    /// Names of the request fields the backend actually filtered on, when it reports them
+    #[serde(default)]
+    pub applied_filters: Option<Vec<String>>,
+}
+
+/// Full-result-set statistics; either part may be missing if the backend doesn't compute it
+#[derive(Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(default)]
+pub struct SearchAggregates {
+    pub price_per_kg: Option<PriceStats>,
+    pub price: Option<PriceStats>,
+}
+
+/// Summary of a set of amounts in cents
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+pub struct PriceStats {
+    pub min: Cents,
+    pub median: Cents,
+    pub avg: Cents,
+    pub max: Cents,
+    /// How many products the statistics were computed from
+    pub count: u64,
+}
+
+impl PriceStats {
+    /// $/kg statistics over just the given products, skipping any without a $/kg
+    pub fn price_per_kg_of(products: &[Product]) -> Option<Self> {
+        Self::from_values(
+            products
+                .iter()
+                .filter_map(|p| p.displayable_price_per_kg())
+                .map(|ppk| ppk.0)
+                .filter(|&v| v > 0)
+                .collect(),
+        )
+    }
+
+    fn from_values(mut values: Vec<u32>) -> Option<Self> {
+        if values.is_empty() {
+            return None;
+        }
+
+        values.sort_unstable();
+
+        let mid = values.len() / 2;
+        let median = if values.len().is_multiple_of(2) {
+            (values[mid - 1] + values[mid]) / 2
+        } else {
+            values[mid]
+        };
+        let sum: u64 = values.iter().map(|&v| v as u64).sum();
+
+        Some(PriceStats {
+            min: Cents(values[0]),
+            median: Cents(median),
+            avg: Cents((sum / values.len() as u64) as u32),
+            max: Cents(values[values.len() - 1]),
+            count: values.len() as u64,
+        })
+    }
+}
+
+/// Min/median/average/max of `label`, labelled with how much of the result set they cover
+#[component]
+fn StatsStrip(
+    label: String,
+    stats: PriceStats,
+    full_set: bool,
+    total: u32,
+    currency: Currency,
+) -> impl IntoView {
+    if stats.count < MIN_STATS_SAMPLES {
+        return ().into_any();
+    }
+
+    let coverage = if full_set {
+        format!("across all {} priced results", stats.count)
+    } else {
+        format!("based on {} of {total} results", stats.count)
+    };
+
+    view! {
+        <div class="stats-strip">
+            <span>{label} " — "</span>
+            <span>"Min " {stats.min.format_in(currency)}</span>
+            <span>"Median " {stats.median.format_in(currency)}</span>
+            <span>"Avg " {stats.avg.format_in(currency)}</span>
+            <span>"Max " {stats.max.format_in(currency)}</span>
+            <span class="hint">{coverage}</span>
+        </div>
+    }
+    .into_any()
+}
+
+/// Materials to leave out of the results, for "everything except TPU and Nylon". Listings with
+/// no material have their own checkbox, so `Unspecified` isn't offered here.
+#[component]
+fn ExcludeMaterials(
+    #[prop(into)] excluded: Signal<Vec<FilamentMaterial>>,
+    #[prop(into)] set_excluded: SignalSetter<Vec<FilamentMaterial>>,
+    /// False while a single material is chosen, which makes the exclusions moot
+    #[prop(into)]
+    active: Signal<bool>,
+) -> impl IntoView {
+    let toggle = move |m: FilamentMaterial, checked: bool| {
+        let mut list = excluded.get_untracked();
+        list.retain(|x| *x != m);
+        if checked {
+            list.push(m);
+        }
+        // Kept in the built-in order, so the URL doesn't depend on click order
+        list.sort_by_key(|x| KNOWN_MATERIALS.iter().position(|k| k == x));
+        set_excluded.set(list);
+    };
+
+    view! {
+        <fieldset class="exclude-materials" prop:disabled=move || !active.get()>
+            <legend>"Exclude materials"</legend>
+            {KNOWN_MATERIALS
+                .iter()
+                .map(|m| {
+                    let m = m.clone();
+                    let name = m.to_string();
+                    let is_excluded = {
+                        let m = m.clone();
+                        move || excluded.with(|e| e.contains(&m))
+                    };
+
+                    view! {
+                        <label class="checkbox-label">
+                            <input
+                                type="checkbox"
+                                prop:checked=is_excluded
+                                on:change=move |e| toggle(m.clone(), event_target_checked(&e))
+                            />
+                            <span>{name}</span>
+                        </label>
+                    }
+                })
+                .collect_view()}
+            <Show when=move || !active.get() && excluded.with(|e| !e.is_empty())>
+                <small class="hint">"Not applied while a single material is chosen"</small>
+            </Show>
+        </fieldset>
+    }
 }
 
 #[component]
 pub fn RangeSlider(
-    min_value: ReadSignal<u32>,
-    set_min_value: WriteSignal<u32>,
-    max_value: ReadSignal<u32>,
-    set_max_value: WriteSignal<u32>,
+    #[prop(into)] min_value: Signal<u32>,
+    #[prop(into)] set_min_value: SignalSetter<u32>,
+    #[prop(into)] max_value: Signal<u32>,
+    #[prop(into)] set_max_value: SignalSetter<u32>,
+    #[prop(into)] currency: Signal<Currency>,
     min_limit: u32,
     max_limit: u32,
     step: u32,
@@ -900,8 +3146,8 @@ pub fn RangeSlider(
     let on_min_input = move |ev: web_sys::Event| {
         if let Ok(mut v) = event_target_value(&ev).parse::<u32>() {
             v = v.clamp(min_limit, max_limit);
-            if v > max_value.get() - gap {
-                v = (max_value.get() - gap).max(min_limit);
+            if v > max_value.get().saturating_sub(gap) {
+                v = (max_value.get().saturating_sub(gap)).max(min_limit);
             }
             set_min_value.set(v);
         }
@@ -921,22 +3167,18 @@ pub fn RangeSlider(
         <div>
             <div class="input-box">
                 <div class="min-box">
-                    <div>
-                        "Min $"
-                    </div>
+                    <div>{move || format!("Min {}", currency.get().symbol())}</div>
                     <input
                         type="number"
                         class="min-input"
                         prop:value=move || min_value.get().to_string()
                         min=min_limit
-                        prop:max=move || (max_value.get() - gap).to_string()
+                        prop:max=move || (max_value.get().saturating_sub(gap)).to_string()
                         on:input=on_min_input
                     />
                 </div>
                 <div class="max-box">
-                    <div>
-                        "Max $"
-                    </div>
+                    <div>{move || format!("Max {}", currency.get().symbol())}</div>
                     <input
                         type="number"
                         class="max-input"
@@ -957,8 +3199,8 @@ pub fn RangeSlider(
                     prop:value=move || min_value.get().to_string()
                     on:input=move |ev| {
                         if let Ok(mut v) = event_target_value(&ev).parse::<u32>() {
-                            if v > max_value.get() - gap {
-                                v = max_value.get() - gap;
+                            if v > max_value.get().saturating_sub(gap) {
+                                v = max_value.get().saturating_sub(gap);
                             }
                             set_min_value.set(v);
                         }
@@ -983,3 +3225,417 @@ pub fn RangeSlider(
         </div>
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::product::WeightBasis;
+
+    #[test]
+    fn other_material_goes_into_request() {
+        let filters = FilterState {
+            material: MaterialFilter::Other("PVA".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            filters.to_request(1, PER_PAGE).material,
+            Some(FilamentMaterial::Other("PVA".to_string()))
+        );
+    }
+
+    #[test]
+    fn empty_other_material_is_no_filter() {
+        for other in ["", "   "] {
+            let filters = FilterState {
+                material: MaterialFilter::Other(other.to_string()),
+                ..Default::default()
+            };
+
+            assert_eq!(filters.to_request(1, PER_PAGE).material, None);
+        }
+    }
+
+    #[test]
+    fn whole_dollar_prices_become_cents() {
+        let filters = FilterState {
+            min_price: 15,
+            max_price: 40,
+            ..Default::default()
+        };
+        let request = filters.to_request(2, 25);
+
+        assert_eq!(request.min_price, Some(Cents(1500)));
+        assert_eq!(request.max_price, Some(Cents(4000)));
+        assert_eq!(request.page, 2);
+        assert_eq!(request.per_page, 25);
+    }
+
+    #[test]
+    fn url_numbers_reject_negative_and_malformed() {
+        for input in ["-1", "", " ", "1.5", "1e3", "abc", "+5"] {
+            assert_eq!(parse_url_number(input), None, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn url_numbers_saturate_on_overflow() {
+        assert_eq!(parse_url_number("42"), Some(42));
+        assert_eq!(
+            parse_url_number("999999999999999999999999999"),
+            Some(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn huge_prices_in_url_are_clamped() {
+        let filters = FilterState::from_query("min_price=999999999999&max_price=999999999999");
+
+        assert_eq!(filters.max_price, MAX_PRICE_CAP);
+        assert_eq!(filters.min_price, MAX_PRICE_CAP - PRICE_GAP);
+        assert!(filters.to_request(1, PER_PAGE).max_price.is_some());
+    }
+
+    #[test]
+    fn negative_or_malformed_prices_in_url_are_ignored() {
+        let filters = FilterState::from_query("min_price=-5&max_price=ten");
+
+        assert_eq!(filters.min_price, FilterState::default().min_price);
+        assert_eq!(filters.max_price, FilterState::default().max_price);
+    }
+
+    #[test]
+    fn zero_max_price_keeps_a_gap() {
+        let filters = FilterState::from_query("min_price=50&max_price=0");
+
+        assert_eq!(filters.min_price, 0);
+        assert_eq!(filters.max_price, PRICE_GAP);
+    }
+
+    #[test]
+    fn page_in_url_is_at_least_one() {
+        assert_eq!(query_page("page=-1"), None);
+        assert_eq!(query_page("page=0"), Some(1));
+        assert_eq!(query_page("page=99999999999"), Some(u32::MAX));
+    }
+
+    #[test]
+    fn whole_dollars_overflow_is_no_price() {
+        assert_eq!(Cents::from_whole_dollars(u32::MAX), None);
+        assert_eq!(Cents::from_whole_dollars(100), Some(Cents(10_000)));
+    }
+
+    #[test]
+    fn page_past_total_goes_to_last_page() {
+        assert_eq!(page_past_end(99_999, 8), Some(8));
+        assert_eq!(page_past_end(9, 8), Some(8));
+    }
+
+    #[test]
+    fn page_within_total_is_kept() {
+        assert_eq!(page_past_end(1, 8), None);
+        assert_eq!(page_past_end(8, 8), None);
+    }
+
+    #[test]
+    fn no_results_still_has_page_one() {
+        assert_eq!(page_past_end(1, 0), None);
+        assert_eq!(page_past_end(3, 0), Some(1));
+    }
+
+    #[test]
+    fn slider_drag_replaces_history_entry() {
+        let mut history = UrlHistory::new(1);
+
+        // Each settled step of a drag searches and writes the URL again
+        let pushes = (0..10).filter(|_| history.is_push(1)).count();
+
+        assert_eq!(pushes, 0);
+    }
+
+    #[test]
+    fn explicit_search_pushes_once() {
+        let mut history = UrlHistory::new(1);
+
+        history.push_next();
+        assert!(history.is_push(1));
+        // A slider change after Seek replaces the entry Seek added
+        assert!(!history.is_push(1));
+    }
+
+    #[test]
+    fn page_change_pushes() {
+        let mut history = UrlHistory::new(1);
+
+        assert!(history.is_push(2));
+        assert!(!history.is_push(2));
+        assert!(history.is_push(1));
+    }
+
+    fn response_applying(filters: &[&str]) -> ProductSearchResponse {
+        ProductSearchResponse {
+            items: vec![],
+            total: 0,
+            total_pages: 0,
+            aggregates: None,
+            applied_filters: Some(filters.iter().map(|f| f.to_string()).collect()),
+        }
+    }
+
+    #[test]
+    fn default_price_cap_is_not_unapplied() {
+        let request = FilterState::default().to_request(1, PER_PAGE);
+
+        assert!(
+            request
+                .unapplied_filters(&response_applying(&[]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn lowered_price_cap_is_unapplied_when_ignored() {
+        let request = FilterState {
+            max_price: 40,
+            ..Default::default()
+        }
+        .to_request(1, PER_PAGE);
+
+        assert_eq!(
+            request.unapplied_filters(&response_applying(&[])),
+            ["max_price"]
+        );
+        assert!(
+            request
+                .unapplied_filters(&response_applying(&["max_price"]))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn page_correction_replaces_bogus_page() {
+        let mut history = UrlHistory::new(1);
+
+        // A `?page=99999` link was followed, and its URL written before the total arrived
+        assert!(history.is_push(99_999));
+        history.correct_page(8);
+
+        assert!(!history.is_push(8));
+    }
+
+    #[test]
+    fn page_correction_keeps_pending_search_entry() {
+        let mut history = UrlHistory::new(1);
+
+        // Seek on a stale page, corrected before its write happened
+        history.push_next();
+        history.correct_page(8);
+
+        assert!(history.is_push(8));
+    }
+
+    #[test]
+    fn changed_filters_search_from_page_one() {
+        let searched = FilterState::default();
+        let current = FilterState {
+            on_sale_only: true,
+            ..Default::default()
+        };
+
+        assert_eq!(search_page(Some(&searched), &current, 5), 1);
+    }
+
+    #[test]
+    fn same_filters_keep_page() {
+        let searched = FilterState::default();
+        let current = searched.clone();
+
+        assert_eq!(search_page(Some(&searched), &current, 5), 5);
+        // The first search, e.g. from a `?page=5` link, has nothing to compare against
+        assert_eq!(search_page(None, &searched, 5), 5);
+    }
+
+    #[test]
+    fn default_filters_browse_everything() {
+        let request = FilterState::default().to_request(1, PER_PAGE);
+
+        assert_eq!(request.name, None);
+        assert_eq!(request.material, None);
+        assert_eq!(request.color, None);
+        assert!(request.is_browsing_all());
+    }
+
+    #[test]
+    fn aggregates_are_not_requested_by_default() {
+        assert_eq!(
+            FilterState::default().to_request(1, PER_PAGE).aggregates,
+            None
+        );
+    }
+
+    fn product(uuid: &str, name: &str) -> Product {
+        Product {
+            uuid: uuid.to_string(),
+            name: name.to_string(),
+            price: Cents(1999),
+            list_price: None,
+            price_per_kg: Cents(1999),
+            currency: Currency::Usd,
+            url: "https://example.com/p".to_string(),
+            material: FilamentMaterial::PLA,
+            diameter: FilamentDiameter::D175,
+            weight: Grams(1000),
+            retailer: Retailer::Other("example.com".to_string()),
+            retailer_product_id: String::new(),
+            color: FilamentColor::Black,
+            weight_basis: WeightBasis::NetFilament,
+            empty_spool_weight: None,
+            in_stock: None,
+            ships_to: vec![],
+            tags: vec![],
+            nozzle_temp: None,
+            bed_temp: None,
+            description: None,
+            updated_at: None,
+            updated_by: None,
+            price_updated_at: None,
+        }
+    }
+
+    #[test]
+    fn duplicate_uuids_keep_the_first() {
+        let mut products = vec![
+            product("a", "first a"),
+            product("b", "b"),
+            product("a", "second a"),
+            product("c", "c"),
+            product("b", "second b"),
+        ];
+
+        assert_eq!(dedupe_by_uuid(&mut products), 2);
+
+        let names: Vec<&str> = products.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["first a", "b", "c"]);
+    }
+
+    #[test]
+    fn unique_uuids_are_untouched() {
+        let mut products = vec![product("a", "a"), product("b", "b")];
+
+        assert_eq!(dedupe_by_uuid(&mut products), 0);
+        assert_eq!(products.len(), 2);
+    }
+
+    fn assert_round_trips(filters: FilterState) {
+        let query = filters.to_query();
+
+        assert_eq!(FilterState::from_query(&query), filters, "{query}");
+    }
+
+    #[test]
+    fn default_filters_round_trip_as_empty_query() {
+        assert_eq!(FilterState::default().to_query(), "");
+        assert_round_trips(FilterState::default());
+    }
+
+    #[test]
+    fn chosen_filters_round_trip() {
+        assert_round_trips(FilterState {
+            query: "matte black".to_string(),
+            min_price: 10,
+            max_price: 40,
+            currency: Currency::Eur,
+            material: MaterialFilter::Materials(vec![
+                FilamentMaterial::PLA,
+                FilamentMaterial::PETG,
+            ]),
+            color: ColorFilter::Material(FilamentColor::Black),
+            diameter: DiameterFilter::D175,
+            weight: WeightFilter::G1000,
+            on_sale_only: true,
+            exclude_unspecified_material: true,
+            exclude_unspecified_color: true,
+            exclude_materials: vec![],
+            tags: vec!["silk".to_string(), "refill".to_string()],
+            tag_match: TagMatch::All,
+            sort: vec![SortKey::asc(SortBy::Price)],
+        });
+    }
+
+    #[test]
+    fn other_material_and_color_round_trip() {
+        assert_round_trips(FilterState {
+            material: MaterialFilter::Other("PVA & HIPS".to_string()),
+            color: ColorFilter::Other("Galaxy Purple".to_string()),
+            diameter: DiameterFilter::Other("3.00".to_string()),
+            weight: WeightFilter::Other("250".to_string()),
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn unspecified_filters_round_trip() {
+        assert_round_trips(FilterState {
+            material: MaterialFilter::Unspecified,
+            color: ColorFilter::Unspecified,
+            diameter: DiameterFilter::Unspecified,
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn excluded_materials_and_sorts_round_trip() {
+        assert_round_trips(FilterState {
+            exclude_materials: vec![FilamentMaterial::ABS, FilamentMaterial::Nylon],
+            sort: vec![SortKey {
+                by: SortBy::PricePerKg,
+                direction: SortDirection::Desc,
+            }],
+            ..Default::default()
+        });
+        assert_round_trips(FilterState {
+            sort: vec![
+                SortKey::asc(SortBy::Material),
+                SortKey {
+                    by: SortBy::Price,
+                    direction: SortDirection::Desc,
+                },
+            ],
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn markdown_export_uses_plain_decimals_in_any_currency() {
+        let product = Product {
+            price: Cents(129_900),
+            price_per_kg: Cents(129_900),
+            currency: Currency::Eur,
+            ..product("a", "Bulk PLA")
+        };
+
+        let table = results_as_markdown(&[product]);
+        let row = table.lines().nth(2).unwrap();
+
+        assert!(row.contains("| 1299.00 | 1299.00 | EUR "), "{row}");
+        assert!(!row.contains('€'), "{row}");
+    }
+
+    #[test]
+    fn result_range_uses_requested_page_size() {
+        assert_eq!(result_range(1, 25, 60), (1, 25));
+        assert_eq!(result_range(2, 25, 60), (26, 50));
+        assert_eq!(result_range(3, 25, 60), (51, 60));
+    }
+
+    #[test]
+    fn result_range_of_huge_page_stays_in_range() {
+        assert_eq!(result_range(u32::MAX, 25, 60), (60, 60));
+        assert_eq!(result_range(u32::MAX, u32::MAX, 60), (60, 60));
+    }
+
+    #[test]
+    fn result_range_of_no_results() {
+        assert_eq!(result_range(1, 25, 0), (0, 0));
+    }
+}
@@ -1,7 +1,15 @@
+use std::{cell::RefCell, collections::BTreeMap};
+
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
 use gloo_net::http::{Method, Request, RequestBuilder};
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use web_sys::{js_sys::Reflect, wasm_bindgen::JsValue};
 
-use crate::{env::API_BASE_URL, session::Session};
+use crate::{
+    env::API_BASE_URL,
+    error_report,
+    session::{self, Session},
+};
 
 #[derive(PartialEq)]
 pub enum Auth {
@@ -10,14 +18,57 @@ pub enum Auth {
     Ephemeral { access_token: String },
 }
 
+#[derive(Clone, Debug)]
 pub struct ErrorResponse {
     pub message: String,
     pub status: u16,
+    /// Per-field validation messages keyed by the request field's name, when the backend
+    /// rejects a body it could parse (usually a 400 or 422)
+    pub fields: BTreeMap<String, String>,
+}
+
+/// Headers `request_json` sets itself, which extra headers may not replace
+const RESERVED_HEADERS: &[&str] = &["Content-Type", "Authorization"];
+/// Starts the message of errors where the request never got a response
+const NETWORK_ERROR_PREFIX: &str = "Network error";
+/// How `auth/token` says the password was right but a two-factor code is needed too
+const MFA_REQUIRED_STATUS: u16 = 428;
+const MFA_REQUIRED_CODE: &str = "mfa_required";
+
+type RefreshFuture = Shared<LocalBoxFuture<'static, Result<(), ErrorResponse>>>;
+
+thread_local! {
+    /// The token refresh in flight. Requests that need one while it runs wait for it rather than
+    /// sending the same refresh token again, which a backend that rotates them would reject.
+    static REFRESH: RefCell<Option<RefreshFuture>> = const { RefCell::new(None) };
+}
+
+impl ErrorResponse {
+    /// The error for a request that never got a response
+    pub fn network(cause: impl std::fmt::Display) -> Self {
+        ErrorResponse {
+            message: format!("{NETWORK_ERROR_PREFIX}: {cause}"),
+            status: 0,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Whether the request failed to reach the server, so sending it again may succeed
+    pub fn is_network_error(&self) -> bool {
+        self.status == 0 && self.message.starts_with(NETWORK_ERROR_PREFIX)
+    }
+
+    /// Whether a login needs to be resubmitted with a two-factor code
+    pub fn is_mfa_required(&self) -> bool {
+        self.status == MFA_REQUIRED_STATUS || self.message == MFA_REQUIRED_CODE
+    }
 }
 
 #[derive(Deserialize)]
 struct GenericError {
     error: String,
+    #[serde(default)]
+    fields: BTreeMap<String, String>,
 }
 
 #[derive(Deserialize)]
@@ -32,6 +83,44 @@ pub async fn request_json<B, R>(
     method: Method,
     body: Option<&B>,
 ) -> Result<R, ErrorResponse>
+where
+    R: DeserializeOwned,
+    B: Serialize,
+{
+    request_json_with_headers(path, auth, method, body, &[]).await
+}
+
+/// [`request_json`] with extra headers for this call, e.g. an `Idempotency-Key` or `If-Match`.
+/// They go with every attempt, including the one after a token refresh. `Content-Type` and
+/// `Authorization` can't be overridden this way.
+pub async fn request_json_with_headers<B, R>(
+    path: &str,
+    auth: Auth,
+    method: Method,
+    body: Option<&B>,
+    headers: &[(&str, &str)],
+) -> Result<R, ErrorResponse>
+where
+    R: DeserializeOwned,
+    B: Serialize,
+{
+    let result = send_with_refresh(path, auth, method, body, headers).await;
+
+    if let Err(err) = &result {
+        error_report::report_api_error(path, err);
+    }
+
+    result
+}
+
+/// Sends the request, refreshing the access token and retrying once if it was rejected
+async fn send_with_refresh<B, R>(
+    path: &str,
+    auth: Auth,
+    method: Method,
+    body: Option<&B>,
+    headers: &[(&str, &str)],
+) -> Result<R, ErrorResponse>
 where
     R: DeserializeOwned,
     B: Serialize,
@@ -41,6 +130,7 @@ where
         auth: &Auth,
         method: Method,
         body: Option<&B>,
+        headers: &[(&str, &str)],
     ) -> Result<Result<R, ErrorResponse>, ErrorResponse>
     where
         R: DeserializeOwned,
@@ -50,11 +140,25 @@ where
             .method(method)
             .header("Content-Type", "application/json");
 
+        for (name, value) in headers {
+            // The auth and refresh handling own these; the value may be a token, so it isn't logged
+            if RESERVED_HEADERS
+                .iter()
+                .any(|r| r.eq_ignore_ascii_case(name))
+            {
+                crate::console_warn(format!("Ignoring extra header {name} for {path}"));
+                continue;
+            }
+
+            req = req.header(name, value);
+        }
+
         match auth {
             Auth::Authorized => {
                 let session = Session::load().ok_or(ErrorResponse {
                     message: "No session in storage".to_string(),
                     status: 0,
+                    fields: BTreeMap::new(),
                 })?;
 
                 req = req.header("Authorization", &format!("Bearer {}", session.access_token));
@@ -69,23 +173,28 @@ where
             req.json(&body).map_err(|e| ErrorResponse {
                 message: format!("Bad JSON: {e}"),
                 status: 0,
+                fields: BTreeMap::new(),
             })?
         } else {
             req.build().map_err(|e| ErrorResponse {
                 message: format!("Request build error: {e}"),
                 status: 0,
+                fields: BTreeMap::new(),
             })?
         };
 
-        let resp = req.send().await.map_err(|e| ErrorResponse {
-            message: format!("Network error: {e}"),
-            status: 0,
-        })?;
+        let resp = req.send().await.map_err(ErrorResponse::network)?;
+
+        // Only visible when the API exposes it via Access-Control-Expose-Headers
+        if let Some(date) = resp.headers().get("Date") {
+            session::record_server_date(&date);
+        }
 
         if resp.ok() {
             let out = resp.json::<R>().await.map_err(|e| ErrorResponse {
                 message: format!("Bad JSON: {e}"),
                 status: resp.status(),
+                fields: BTreeMap::new(),
             })?;
             Ok(Ok(out))
         } else {
@@ -95,17 +204,26 @@ where
                 Ok(e) => ErrorResponse {
                     message: e.error,
                     status,
+                    fields: e.fields,
                 },
                 Err(e) => ErrorResponse {
                     message: format!("Bad JSON: {e}"),
                     status,
+                    fields: BTreeMap::new(),
                 },
             };
             Ok(Err(err))
         }
     }
 
-    match send_once::<B, R>(path, &auth, method.clone(), body).await? {
+    if auth == Auth::Authorized
+        && Session::load().is_some_and(|s| s.access_token_expiring())
+        && refresh_access_token().await.is_ok()
+    {
+        crate::console_log("Access token refreshed ahead of expiry");
+    }
+
+    match send_once::<B, R>(path, &auth, method.clone(), body, headers).await? {
         Ok(ok) => Ok(ok),
         Err(err) if err.status == 401 && auth == Auth::Authorized => {
             if refresh_access_token().await.is_err() {
@@ -119,7 +237,7 @@ where
                 web_sys::window()
                     .expect("No global window")
                     .location()
-                    .set_href("/login")
+                    .set_href(&format!("/login?reason={}", crate::login::SESSION_EXPIRED))
                     .expect("Failed to redirect to login page");
 
                 return Err(err);
@@ -127,7 +245,7 @@ where
                 crate::console_log("Access token refreshed");
             }
 
-            match send_once::<B, R>(path, &auth, method, body).await? {
+            match send_once::<B, R>(path, &auth, method, body, headers).await? {
                 Ok(r) => Ok(r),
                 Err(_) => Err(err),
             }
@@ -136,10 +254,64 @@ where
     }
 }
 
+/// A fresh key for an `Idempotency-Key` header. Send the same key when repeating a request, so
+/// the backend can tell it's the same attempt and not act on it twice.
+pub fn new_idempotency_key() -> String {
+    // crypto.randomUUID only exists in secure contexts, and calling through a missing one would
+    // throw. A random version 4 UUID either way.
+    web_sys::window()
+        .and_then(|w| w.crypto().ok())
+        .filter(|c| {
+            Reflect::get(c, &JsValue::from_str("randomUUID")).is_ok_and(|f| f.is_function())
+        })
+        .map(|c| c.random_uuid())
+        .unwrap_or_else(|| {
+            let hex = |n: usize| -> String {
+                (0..n)
+                    .map(|_| {
+                        let digit = (web_sys::js_sys::Math::random() * 16.0) as u32;
+                        char::from_digit(digit.min(15), 16).unwrap_or('0')
+                    })
+                    .collect()
+            };
+            let variant =
+                ['8', '9', 'a', 'b'][(web_sys::js_sys::Math::random() * 4.0) as usize % 4];
+
+            format!(
+                "{}-{}-4{}-{variant}{}-{}",
+                hex(8),
+                hex(4),
+                hex(3),
+                hex(3),
+                hex(12)
+            )
+        })
+}
+
+/// Refreshes the access token, joining the refresh already in flight if there is one
 async fn refresh_access_token() -> Result<(), ErrorResponse> {
+    let refresh = REFRESH.with_borrow_mut(|r| {
+        r.get_or_insert_with(|| send_refresh().boxed_local().shared())
+            .clone()
+    });
+
+    let result = refresh.clone().await;
+
+    // Whoever gets here first clears it, so the next expiry starts a new refresh
+    REFRESH.with_borrow_mut(|r| {
+        if r.as_ref().is_some_and(|r| r.ptr_eq(&refresh)) {
+            *r = None;
+        }
+    });
+
+    result
+}
+
+async fn send_refresh() -> Result<(), ErrorResponse> {
     let mut session = Session::load().ok_or(ErrorResponse {
         message: "No session in storage".to_string(),
         status: 0,
+        fields: BTreeMap::new(),
     })?;
 
     #[derive(Serialize)]
@@ -151,8 +323,8 @@ async fn refresh_access_token() -> Result<(), ErrorResponse> {
 
     let body = RefreshBody {
         grant_type: "refresh_token".to_string(),
-        username: session.username,
-        refresh_token: session.refresh_token,
+        username: session.username.clone(),
+        refresh_token: session.refresh_token.clone(),
     };
 
     let response = Request::post(&format!("{API_BASE_URL}/auth/token"))
@@ -161,13 +333,11 @@ async fn refresh_access_token() -> Result<(), ErrorResponse> {
         .map_err(|e| ErrorResponse {
             message: format!("Bad JSON: {e}"),
             status: 0,
+            fields: BTreeMap::new(),
         })?
         .send()
         .await
-        .map_err(|e| ErrorResponse {
-            message: format!("Network error: {e}"),
-            status: 0,
-        })?;
+        .map_err(ErrorResponse::network)?;
 
     if !response.ok() {
         let status = response.status();
@@ -177,7 +347,11 @@ async fn refresh_access_token() -> Result<(), ErrorResponse> {
             Err(e) => format!("Bad JSON: {e}"),
         };
 
-        return Err(ErrorResponse { message, status });
+        return Err(ErrorResponse {
+            message,
+            status,
+            fields: BTreeMap::new(),
+        });
     }
 
     let response = response
@@ -186,9 +360,15 @@ async fn refresh_access_token() -> Result<(), ErrorResponse> {
         .map_err(|e| ErrorResponse {
             message: format!("Bad JSON: {e}"),
             status: 0,
+            fields: BTreeMap::new(),
         })?;
 
     session.access_token = response.access_token;
     session.refresh_token = response.refresh_token;
-    Ok(())
+
+    session.save().map_err(|e| ErrorResponse {
+        message: format!("Failed to save session: {e}"),
+        status: 0,
+        fields: BTreeMap::new(),
+    })
 }
@@ -0,0 +1,56 @@
+use std::future::Future;
+
+use leptos::{prelude::*, reactive::spawn_local};
+
+use crate::request::ErrorResponse;
+
+#[derive(Clone, Debug)]
+pub enum LoadState<T> {
+    /// The source has nothing to load
+    Idle,
+    Loading,
+    Loaded(T),
+    Failed(ErrorResponse),
+}
+
+/// Runs `fetcher` whenever the signals read by `source` change, exposing the outcome as a
+/// [`LoadState`]. A `None` source leaves the state [`LoadState::Idle`], and responses to
+/// superseded requests are dropped.
+pub fn use_resource<S, T, Fut>(
+    source: impl Fn() -> Option<S> + 'static,
+    fetcher: impl Fn(S) -> Fut + 'static,
+) -> ReadSignal<LoadState<T>>
+where
+    S: 'static,
+    T: Send + Sync + 'static,
+    Fut: Future<Output = Result<T, ErrorResponse>> + 'static,
+{
+    let (state, set_state) = signal(LoadState::Idle);
+    let generation = StoredValue::new(0u64);
+
+    Effect::new(move |_| {
+        generation.update_value(|g| *g += 1);
+        let current = generation.get_value();
+
+        let Some(source) = source() else {
+            set_state.set(LoadState::Idle);
+            return;
+        };
+
+        let future = fetcher(source);
+        set_state.set(LoadState::Loading);
+
+        spawn_local(async move {
+            let result = future.await;
+
+            if generation.get_value() == current {
+                set_state.set(match result {
+                    Ok(value) => LoadState::Loaded(value),
+                    Err(e) => LoadState::Failed(e),
+                });
+            }
+        });
+    });
+
+    state
+}
@@ -0,0 +1,195 @@
+use gloo_net::http::Method;
+use leptos::{ev, prelude::*, reactive::spawn_local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    request::{Auth, request_json},
+    session::Session,
+};
+
+#[derive(Serialize)]
+struct RestockAlertBody {
+    product_uuid: String,
+    /// Only sent for visitors who aren't logged in; signed-in alerts go to the account's email
+    #[serde(skip_serializing_if = "Option::is_none")]
+    email: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RestockAlertStatus {
+    subscribed: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum AlertState {
+    Checking,
+    NotSubscribed,
+    Subscribed,
+}
+
+/// "Notify when available" for an out-of-stock product. Signed-in users can subscribe and
+/// unsubscribe; anyone else can leave an email address or log in.
+#[component]
+pub fn RestockAlert(product_uuid: String) -> impl IntoView {
+    let logged_in = Session::is_logged_in();
+    let product_uuid = StoredValue::new(product_uuid);
+
+    let (state, set_state) = signal(if logged_in {
+        AlertState::Checking
+    } else {
+        AlertState::NotSubscribed
+    });
+    let (busy, set_busy) = signal(false);
+    let (email, set_email) = signal(String::new());
+    let (message, set_message) = signal::<Option<Result<String, String>>>(None);
+
+    if logged_in {
+        spawn_local(async move {
+            let path = format!("restock-alerts/{}", product_uuid.get_value());
+
+            match request_json::<(), RestockAlertStatus>(&path, Auth::Authorized, Method::GET, None)
+                .await
+            {
+                Ok(status) if status.subscribed => set_state.set(AlertState::Subscribed),
+                Ok(_) => set_state.set(AlertState::NotSubscribed),
+                Err(e) if e.status == 404 => set_state.set(AlertState::NotSubscribed),
+                Err(e) => {
+                    set_state.set(AlertState::NotSubscribed);
+                    set_message.set(Some(Err(format!(
+                        "Couldn't check your alerts: ({}) {}",
+                        e.status, e.message
+                    ))));
+                }
+            }
+        });
+    }
+
+    let subscribe = move |email: Option<String>| {
+        let body = RestockAlertBody {
+            product_uuid: product_uuid.get_value(),
+            email,
+        };
+        let auth = if logged_in {
+            Auth::Authorized
+        } else {
+            Auth::Unauthorized
+        };
+
+        set_busy.set(true);
+        set_message.set(None);
+
+        spawn_local(async move {
+            match request_json::<RestockAlertBody, ()>(
+                "restock-alerts",
+                auth,
+                Method::POST,
+                Some(&body),
+            )
+            .await
+            {
+                Ok(()) => {
+                    set_state.set(AlertState::Subscribed);
+                    set_message.set(Some(Ok(
+                        "We'll email you when it's back in stock".to_string()
+                    )));
+                }
+                Err(e) => set_message.set(Some(Err(format!(
+                    "Couldn't set up the alert: ({}) {}",
+                    e.status, e.message
+                )))),
+            }
+
+            set_busy.set(false);
+        });
+    };
+
+    let on_unsubscribe = move |_| {
+        let path = format!("restock-alerts/{}", product_uuid.get_value());
+
+        set_busy.set(true);
+        set_message.set(None);
+
+        spawn_local(async move {
+            match request_json::<(), ()>(&path, Auth::Authorized, Method::DELETE, None).await {
+                Ok(()) => {
+                    set_state.set(AlertState::NotSubscribed);
+                    set_message.set(Some(Ok("Alert removed".to_string())));
+                }
+                Err(e) => set_message.set(Some(Err(format!(
+                    "Couldn't remove the alert: ({}) {}",
+                    e.status, e.message
+                )))),
+            }
+
+            set_busy.set(false);
+        });
+    };
+
+    let on_email_submit = move |e: ev::SubmitEvent| {
+        e.prevent_default();
+
+        let email = email.get_untracked().trim().to_string();
+
+        if !email.contains('@') {
+            set_message.set(Some(Err("Enter an email address".to_string())));
+            return;
+        }
+
+        subscribe(Some(email));
+    };
+
+    let controls = move || match (state.get(), logged_in) {
+        (AlertState::Checking, _) => view! { <p class="hint">"Checking alerts…"</p> }.into_any(),
+        (AlertState::Subscribed, true) => view! {
+            <p>
+                "You'll be notified when it's available. "
+                <button class="link-button" disabled=move || busy.get() on:click=on_unsubscribe>
+                    "Stop notifying me"
+                </button>
+            </p>
+        }
+        .into_any(),
+        // Anonymous alerts are cancelled from the link in the email
+        (AlertState::Subscribed, false) => ().into_any(),
+        (AlertState::NotSubscribed, true) => view! {
+            <button disabled=move || busy.get() on:click=move |_| subscribe(None)>
+                "Notify when available"
+            </button>
+        }
+        .into_any(),
+        (AlertState::NotSubscribed, false) => view! {
+            <form class="options-row" on:submit=on_email_submit>
+                <input
+                    class="input"
+                    type="email"
+                    placeholder="you@example.com"
+                    aria-label="Email for the restock alert"
+                    prop:value=move || email.get()
+                    on:input=move |e| set_email.set(event_target_value(&e))
+                />
+                <button type="submit" disabled=move || busy.get()>
+                    "Notify when available"
+                </button>
+            </form>
+            <small class="hint">
+                "Or " <a href="/login">"log in"</a> " to manage alerts from your account."
+            </small>
+        }
+        .into_any(),
+    };
+
+    view! {
+        <div class="restock-alert">
+            <p><strong>"Out of stock"</strong></p>
+            {controls}
+            {move || {
+                message
+                    .get()
+                    .map(|m| match m {
+                        Ok(s) => view! { <p class="success" role="status">{s}</p> }.into_any(),
+                        Err(s) => view! { <p class="error" role="alert">{s}</p> }.into_any(),
+                    })
+            }}
+        </div>
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+use gloo_storage::{LocalStorage, Storage};
+use leptos::{prelude::*, reactive::spawn_local};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    product::{Cents, Currency, humanize_age},
+    product_search::{FilterState, try_search_products},
+    toast::use_toasts,
+};
+
+const SAVED_SEARCHES_KEY: &str = "saved_searches_v1";
+/// Results compared per run; the API's largest page
+const SNAPSHOT_SIZE: u32 = 50;
+
+/// A product as it appeared in a run of a saved search
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct SeenProduct {
+    name: String,
+    price: Cents,
+    #[serde(default)]
+    currency: Currency,
+}
+
+/// The results of a saved search the last time its changes were viewed, keyed by UUID
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct Snapshot {
+    taken_at_ms: f64,
+    products: BTreeMap<String, SeenProduct>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SavedSearch {
+    name: String,
+    /// The filters, encoded as by [`FilterState::to_query`]
+    query: String,
+    #[serde(default)]
+    snapshot: Option<Snapshot>,
+}
+
+fn load_saved() -> Vec<SavedSearch> {
+    LocalStorage::get(SAVED_SEARCHES_KEY).unwrap_or_default()
+}
+
+fn store_saved(searches: &[SavedSearch]) {
+    let _ = LocalStorage::set(SAVED_SEARCHES_KEY, searches);
+}
+
+/// What differs between two runs of a search
+#[derive(Clone, Debug, Default)]
+struct SearchChanges {
+    added: Vec<(String, SeenProduct)>,
+    removed: Vec<(String, SeenProduct)>,
+    /// The product as it is now, with its previous price
+    price_changed: Vec<(String, SeenProduct, Cents)>,
+}
+
+impl SearchChanges {
+    /// `new_is_complete` says whether `new` holds every match. When it doesn't, a product missing
+    /// from it may just have been pushed past the snapshot, so none are reported as removed.
+    fn between(
+        old: &BTreeMap<String, SeenProduct>,
+        new: &BTreeMap<String, SeenProduct>,
+        new_is_complete: bool,
+    ) -> Self {
+        let mut changes = SearchChanges::default();
+
+        for (uuid, now) in new {
+            match old.get(uuid) {
+                None => changes.added.push((uuid.clone(), now.clone())),
+                Some(before) if before.price != now.price => {
+                    changes
+                        .price_changed
+                        .push((uuid.clone(), now.clone(), before.price))
+                }
+                Some(_) => (),
+            }
+        }
+
+        for (uuid, before) in old.iter().filter(|_| new_is_complete) {
+            if !new.contains_key(uuid) {
+                changes.removed.push((uuid.clone(), before.clone()));
+            }
+        }
+
+        changes
+    }
+
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.price_changed.is_empty()
+    }
+}
+
+/// Outcome of running a saved search against its snapshot
+#[derive(Clone, Debug)]
+enum Comparison {
+    Running,
+    /// No earlier run to compare with; this run becomes the baseline
+    FirstRun {
+        recorded: usize,
+    },
+    Changed {
+        since_ms: f64,
+        changes: SearchChanges,
+        /// Whether the search had more matches than a snapshot holds
+        partial: bool,
+    },
+    Failed(String),
+}
+
+/// Saves the current filters under the results heading, so their changes can be followed later
+#[component]
+pub fn SaveSearchButton(filters: RwSignal<FilterState>) -> impl IntoView {
+    let toasts = use_toasts();
+
+    let on_click = move |_| {
+        let (name, query) = filters.with_untracked(|f| {
+            (
+                f.to_request(1, SNAPSHOT_SIZE).results_heading(),
+                f.to_query(),
+            )
+        });
+        let mut saved = load_saved();
+
+        if saved.iter().any(|s| s.query == query) {
+            toasts.success("This search is already saved");
+            return;
+        }
+
+        saved.push(SavedSearch {
+            name,
+            query,
+            snapshot: None,
+        });
+        store_saved(&saved);
+        toasts.success("Search saved");
+    };
+
+    view! {
+        <button class="link-button" on:click=on_click>
+            "Save search"
+        </button>
+    }
+}
+
+fn product_link(uuid: &str, product: &SeenProduct) -> impl IntoView + use<> {
+    view! { <a href=format!("/product/{uuid}")>{product.name.clone()}</a> }
+}
+
+#[component]
+pub fn SavedSearchesPage() -> impl IntoView {
+    let saved = RwSignal::new(load_saved());
+    let comparison = RwSignal::new(None::<(String, Comparison)>);
+
+    let compare = move |index: usize| {
+        let Some(search) = saved.with_untracked(|s| s.get(index).cloned()) else {
+            return;
+        };
+
+        comparison.set(Some((search.name.clone(), Comparison::Running)));
+
+        spawn_local(async move {
+            let request = FilterState::from_query(&search.query).to_request(1, SNAPSHOT_SIZE);
+
+            let response = match try_search_products(&request).await {
+                Ok(r) => r,
+                Err(e) => {
+                    let message = format!("Failed to run search: ({}) {}", e.status, e.message);
+                    comparison.set(Some((search.name, Comparison::Failed(message))));
+                    return;
+                }
+            };
+
+            let partial = response.total > u64::from(SNAPSHOT_SIZE);
+            let current: BTreeMap<String, SeenProduct> = response
+                .items
+                .into_iter()
+                .map(|p| {
+                    (
+                        p.uuid,
+                        SeenProduct {
+                            name: p.name,
+                            price: p.price,
+                            currency: p.currency,
+                        },
+                    )
+                })
+                .collect();
+
+            let result = match &search.snapshot {
+                Some(snapshot) => Comparison::Changed {
+                    since_ms: snapshot.taken_at_ms,
+                    changes: SearchChanges::between(&snapshot.products, &current, !partial),
+                    partial,
+                },
+                None => Comparison::FirstRun {
+                    recorded: current.len(),
+                },
+            };
+
+            // Viewing the changes makes this run the one the next comparison starts from
+            saved.update(|s| {
+                if let Some(entry) = s.iter_mut().find(|e| e.query == search.query) {
+                    entry.snapshot = Some(Snapshot {
+                        taken_at_ms: web_sys::js_sys::Date::now(),
+                        products: current,
+                    });
+                }
+                store_saved(s);
+            });
+
+            comparison.set(Some((search.name, result)));
+        });
+    };
+
+    let remove = move |index: usize| {
+        saved.update(|s| {
+            if index < s.len() {
+                s.remove(index);
+            }
+            store_saved(s);
+        });
+    };
+
+    view! {
+        <div class="container">
+            <p><a href="/">"← Back to search"</a></p>
+            <div class="card">
+                <h1>"Saved searches"</h1>
+                {move || {
+                    if saved.with(|s| s.is_empty()) {
+                        return view! {
+                            <div class="empty">"Use “Save search” above the results to keep a search here."</div>
+                        }
+                            .into_any();
+                    }
+
+                    view! {
+                        <ul class="favorites-list">
+                            {saved
+                                .get()
+                                .into_iter()
+                                .enumerate()
+                                .map(|(i, s)| {
+                                    let last_run = s
+                                        .snapshot
+                                        .as_ref()
+                                        .map(|snap| {
+                                            let secs = (web_sys::js_sys::Date::now() - snap.taken_at_ms) / 1000.0;
+                                            format!("Last checked {}", humanize_age(secs.max(0.0)))
+                                        })
+                                        .unwrap_or_else(|| "Not checked yet".to_string());
+
+                                    view! {
+                                        <li class="saved-search">
+                                            // A full load, since the search reads its filters from the URL only when it starts
+                                            <a href=format!("/?{}", s.query) rel="external">{s.name}</a>
+                                            <small class="hint">{last_run}</small>
+                                            <button class="link-button" on:click=move |_| compare(i)>
+                                                "What changed?"
+                                            </button>
+                                            <button class="link-button" on:click=move |_| remove(i)>
+                                                "Remove"
+                                            </button>
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                    }
+                        .into_any()
+                }}
+                {move || comparison.get().map(|(name, result)| view! {
+                    <section class="search-changes">
+                        <h2>{name}</h2>
+                        <ComparisonView result=result />
+                    </section>
+                })}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ComparisonView(result: Comparison) -> impl IntoView {
+    match result {
+        Comparison::Running => view! { <div class="loading">"Checking…"</div> }.into_any(),
+        Comparison::Failed(message) => view! { <p class="error">{message}</p> }.into_any(),
+        Comparison::FirstRun { recorded } => view! {
+            <p class="hint">
+                {format!("Recorded {recorded} results. Changes will show the next time you check.")}
+            </p>
+        }
+        .into_any(),
+        Comparison::Changed { changes, .. } if changes.is_empty() => {
+            view! { <p class="hint">"Nothing changed since the last check."</p> }.into_any()
+        }
+        Comparison::Changed {
+            since_ms,
+            changes,
+            partial,
+        } => {
+            let since = humanize_age(((web_sys::js_sys::Date::now() - since_ms) / 1000.0).max(0.0));
+
+            view! {
+                <p class="hint">{format!("Compared with the check from {since}")}</p>
+                {partial.then(|| view! {
+                    <p class="hint">
+                        {format!("Only the first {SNAPSHOT_SIZE} results are compared, so products that dropped out of them aren't listed.")}
+                    </p>
+                })}
+                {(!changes.added.is_empty()).then(|| view! {
+                    <h3>"New"</h3>
+                    <ul>
+                        {changes.added.iter().map(|(uuid, p)| view! {
+                            <li>{product_link(uuid, p)} " — " {p.price.format_in(p.currency)}</li>
+                        }).collect_view()}
+                    </ul>
+                })}
+                {(!changes.price_changed.is_empty()).then(|| view! {
+                    <h3>"Price changed"</h3>
+                    <ul>
+                        {changes.price_changed.iter().map(|(uuid, p, old)| view! {
+                            <li>
+                                {product_link(uuid, p)}
+                                {format!(" — {} → {}", old.format_in(p.currency), p.price.format_in(p.currency))}
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                })}
+                {(!changes.removed.is_empty()).then(|| view! {
+                    <h3>"No longer matching"</h3>
+                    <ul>
+                        {changes.removed.iter().map(|(uuid, p)| view! {
+                            <li>{product_link(uuid, p)}</li>
+                        }).collect_view()}
+                    </ul>
+                })}
+            }
+            .into_any()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seen(name: &str, cents: u32) -> SeenProduct {
+        SeenProduct {
+            name: name.to_string(),
+            price: Cents(cents),
+            currency: Currency::Usd,
+        }
+    }
+
+    fn run(products: &[(&str, u32)]) -> BTreeMap<String, SeenProduct> {
+        products
+            .iter()
+            .map(|(uuid, cents)| (uuid.to_string(), seen(uuid, *cents)))
+            .collect()
+    }
+
+    #[test]
+    fn new_product_is_added() {
+        let changes =
+            SearchChanges::between(&run(&[("a", 100)]), &run(&[("a", 100), ("b", 200)]), true);
+
+        assert_eq!(changes.added, [("b".to_string(), seen("b", 200))]);
+        assert!(changes.removed.is_empty());
+        assert!(changes.price_changed.is_empty());
+    }
+
+    #[test]
+    fn missing_product_is_removed() {
+        let changes =
+            SearchChanges::between(&run(&[("a", 100), ("b", 200)]), &run(&[("a", 100)]), true);
+
+        assert_eq!(changes.removed, [("b".to_string(), seen("b", 200))]);
+        assert!(changes.added.is_empty());
+    }
+
+    #[test]
+    fn missing_product_of_partial_run_is_not_removed() {
+        let changes =
+            SearchChanges::between(&run(&[("a", 100), ("b", 200)]), &run(&[("a", 100)]), false);
+
+        assert!(changes.removed.is_empty());
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn price_change_keeps_old_price() {
+        let changes = SearchChanges::between(&run(&[("a", 100)]), &run(&[("a", 90)]), true);
+
+        assert_eq!(
+            changes.price_changed,
+            [("a".to_string(), seen("a", 90), Cents(100))]
+        );
+    }
+
+    #[test]
+    fn unchanged_run_has_no_changes() {
+        let products = run(&[("a", 100), ("b", 200)]);
+
+        assert!(SearchChanges::between(&products, &products, true).is_empty());
+    }
+}
@@ -1,10 +1,29 @@
 use gloo_net::http::Method;
 use gloo_storage::{LocalStorage, Storage};
+use leptos::{ev, prelude::*};
 use serde::{Deserialize, Serialize};
 
-use crate::request::request_json;
+use crate::{
+    env::TOKEN_EXPIRY_SKEW_SECS,
+    request::{ErrorResponse, request_json},
+};
 
-const SESSION_KEY: &str = "session_v1";
+const SESSION_KEY: &str = "session";
+/// Bump this and add an arm to `upgrade_session` whenever `Session` changes shape
+const SESSION_VERSION: u32 = 2;
+/// Where sessions were stored, unversioned, before `SESSION_KEY`; read once and moved over
+const LEGACY_SESSION_V1_KEY: &str = "session_v1";
+const CLOCK_OFFSET_KEY: &str = "clock_offset_v1";
+/// Dispatched on the window when this tab saves or clears the session; other tabs get a
+/// `storage` event instead
+const SESSION_CHANGED_EVENT: &str = "session-changed";
+
+/// A stored session tagged with the schema version it was written with
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    version: u32,
+    session: serde_json::Value,
+}
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Session {
@@ -18,31 +37,92 @@ pub struct Session {
 
 impl Session {
     pub fn save(&self) -> Result<(), gloo_storage::errors::StorageError> {
-        LocalStorage::set(SESSION_KEY, self)
+        let stored = StoredSession {
+            version: SESSION_VERSION,
+            session: serde_json::to_value(self)?,
+        };
+        LocalStorage::set(SESSION_KEY, stored)?;
+        notify_session_changed();
+        Ok(())
     }
 
+    /// Loads the stored session, upgrading one written by an older schema in place.
+    /// Only data that can't be read as any known version is cleared.
     pub fn load() -> Option<Self> {
-        LocalStorage::get(SESSION_KEY).ok()
+        if let Ok(raw) = LocalStorage::get::<serde_json::Value>(SESSION_KEY) {
+            let Ok(stored) = serde_json::from_value::<StoredSession>(raw) else {
+                Session::clear();
+                return None;
+            };
+
+            let action = stored_session_action(&stored);
+            if action == StoredSessionAction::LeaveForNewerDeploy {
+                return None;
+            }
+
+            let Some(session) = upgrade_session(stored.version, stored.session) else {
+                Session::clear();
+                return None;
+            };
+
+            if action == StoredSessionAction::Upgrade {
+                let _ = session.save();
+            }
+
+            return Some(session);
+        }
+
+        let legacy = LocalStorage::get::<serde_json::Value>(LEGACY_SESSION_V1_KEY).ok()?;
+        LocalStorage::delete(LEGACY_SESSION_V1_KEY);
+
+        let session = upgrade_session(1, legacy)?;
+        let _ = session.save();
+        Some(session)
     }
 
     pub fn clear() {
         LocalStorage::delete(SESSION_KEY);
+        LocalStorage::delete(LEGACY_SESSION_V1_KEY);
+        notify_session_changed();
     }
 
     pub fn is_logged_in() -> bool {
         Session::load().is_some()
     }
 
-    pub async fn log_in(access_token: String, refresh_token: String) -> Result<Self, String> {
-        #[derive(Serialize, Deserialize)]
-        struct UserResponse {
-            pub uuid: String,
-            pub username: String,
-            pub email: String,
-            pub is_admin: bool,
+    /// The access token's `exp` claim in seconds since the epoch, if the token is a readable JWT
+    pub fn access_token_expiry(&self) -> Option<f64> {
+        #[derive(Deserialize)]
+        struct Claims {
+            exp: f64,
         }
 
-        let user_response = match request_json::<(), UserResponse>(
+        let payload = self.access_token.split('.').nth(1)?;
+        let mut base64 = payload.replace('-', "+").replace('_', "/");
+
+        while !base64.len().is_multiple_of(4) {
+            base64.push('=');
+        }
+
+        let json = web_sys::window()?.atob(&base64).ok()?;
+        serde_json::from_str::<Claims>(&json).ok().map(|c| c.exp)
+    }
+
+    /// Whether the access token has expired, or will within the `TOKEN_EXPIRY_SKEW_SECS` build
+    /// setting, by the server's clock
+    pub fn access_token_expiring(&self) -> bool {
+        self.access_token_expiry().is_some_and(|exp| {
+            expires_within(
+                web_sys::js_sys::Date::now() / 1000.0,
+                clock_offset_secs(),
+                exp,
+                TOKEN_EXPIRY_SKEW_SECS,
+            )
+        })
+    }
+
+    pub async fn log_in(access_token: String, refresh_token: String) -> Result<Self, String> {
+        let user = request_json::<(), UserResponse>(
             "users/me",
             crate::request::Auth::Ephemeral {
                 access_token: access_token.clone(),
@@ -50,22 +130,351 @@ impl Session {
             Method::GET,
             None,
         )
-        .await
-        {
-            Ok(user) => user,
-            Err(err) => return Err(format!("{}: {}", err.status, err.message)),
-        };
+        .await;
+
+        Session::finish_log_in(user, access_token, refresh_token, |session| {
+            session.save().map_err(|e| e.to_string())
+        })
+    }
 
-        let session = Session {
-            uuid: user_response.uuid,
-            username: user_response.username,
-            email: user_response.email,
-            is_admin: user_response.is_admin,
+    /// Builds the session from `users/me`'s answer and hands it to `save`; nothing is saved when
+    /// the lookup failed
+    fn finish_log_in(
+        user: Result<UserResponse, ErrorResponse>,
+        access_token: String,
+        refresh_token: String,
+        save: impl FnOnce(&Session) -> Result<(), String>,
+    ) -> Result<Self, String> {
+        let session = Session::from_user(user, access_token, refresh_token)?;
+        save(&session)?;
+        Ok(session)
+    }
+
+    /// Builds the session for the tokens from `users/me`'s answer, or the error to show
+    fn from_user(
+        user: Result<UserResponse, ErrorResponse>,
+        access_token: String,
+        refresh_token: String,
+    ) -> Result<Self, String> {
+        let user = user.map_err(|err| format!("{}: {}", err.status, err.message))?;
+
+        Ok(Session {
+            uuid: user.uuid,
+            username: user.username,
+            email: user.email,
+            is_admin: user.is_admin,
             access_token,
             refresh_token,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct UserResponse {
+    uuid: String,
+    username: String,
+    email: String,
+    is_admin: bool,
+}
+
+/// What [`Session::load`] does with a session stored under [`SESSION_KEY`]
+#[derive(Debug, PartialEq)]
+enum StoredSessionAction {
+    Read,
+    /// Read it, then save it back in the current shape
+    Upgrade,
+    /// Written by a newer deploy; left alone rather than logging the user out
+    LeaveForNewerDeploy,
+}
+
+fn stored_session_action(stored: &StoredSession) -> StoredSessionAction {
+    match stored.version {
+        v if v > SESSION_VERSION => StoredSessionAction::LeaveForNewerDeploy,
+        v if v < SESSION_VERSION => StoredSessionAction::Upgrade,
+        _ => StoredSessionAction::Read,
+    }
+}
+
+/// Reads a session stored with schema `version` into the current shape
+fn upgrade_session(version: u32, value: serde_json::Value) -> Option<Session> {
+    match version {
+        // v2 only moved the session under a versioned key; the fields are unchanged
+        1 | SESSION_VERSION => serde_json::from_value(value).ok(),
+        _ => None,
+    }
+}
+
+fn notify_session_changed() {
+    if let (Some(window), Ok(event)) = (
+        web_sys::window(),
+        web_sys::Event::new(SESSION_CHANGED_EVENT),
+    ) {
+        let _ = window.dispatch_event(&event);
+    }
+}
+
+#[derive(Clone, Copy)]
+struct CurrentSession(ReadSignal<Option<Session>>);
+
+/// Keeps the stored session in a signal shared through context, so views follow logins and
+/// logouts without a reload, including ones made in other tabs
+pub fn provide_session() {
+    let (session, set_session) = signal(Session::load());
+
+    let changed = window_event_listener_untyped(SESSION_CHANGED_EVENT, move |_| {
+        set_session.set(Session::load())
+    });
+    let storage = window_event_listener(ev::storage, move |e| {
+        // A `None` key means the whole storage was cleared
+        if e.key().is_none_or(|k| k == SESSION_KEY) {
+            set_session.set(Session::load());
+        }
+    });
+    on_cleanup(move || {
+        changed.remove();
+        storage.remove();
+    });
+
+    provide_context(CurrentSession(session));
+}
+
+/// Whether the signed-in user is an admin, kept up to date as they log in and out
+pub fn use_is_admin() -> Signal<bool> {
+    let session = use_context::<CurrentSession>()
+        .expect("session should be provided by App")
+        .0;
+
+    Memo::new(move |_| session.with(|s| s.as_ref().is_some_and(|s| s.is_admin))).into()
+}
+
+/// Records how far the local clock is behind the server, given a response's `Date` header
+pub fn record_server_date(date_header: &str) {
+    let server_ms = web_sys::js_sys::Date::parse(date_header);
+
+    if server_ms.is_nan() {
+        return;
+    }
+
+    let offset_secs = (server_ms - web_sys::js_sys::Date::now()) / 1000.0;
+    let _ = LocalStorage::set(CLOCK_OFFSET_KEY, offset_secs);
+}
+
+/// How far the local clock was last seen behind the server, in seconds
+fn clock_offset_secs() -> f64 {
+    LocalStorage::get(CLOCK_OFFSET_KEY).unwrap_or(0.0)
+}
+
+/// The current time in seconds since the epoch, corrected by the last recorded server clock offset
+pub fn server_now_secs() -> f64 {
+    web_sys::js_sys::Date::now() / 1000.0 + clock_offset_secs()
+}
+
+/// Whether a token expiring at `exp` is within `skew_secs` of expiry, given the local time and
+/// how far the local clock is behind the server
+fn expires_within(local_now_secs: f64, offset_secs: f64, exp: f64, skew_secs: f64) -> bool {
+    local_now_secs + offset_secs + skew_secs >= exp
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    fn user(is_admin: bool) -> UserResponse {
+        UserResponse {
+            uuid: "u-1".to_string(),
+            username: "ada".to_string(),
+            email: "ada@example.com".to_string(),
+            is_admin,
+        }
+    }
+
+    #[test]
+    fn builds_session_from_user() {
+        let session =
+            Session::from_user(Ok(user(false)), "access".to_string(), "refresh".to_string())
+                .unwrap();
+
+        assert_eq!(session.uuid, "u-1");
+        assert_eq!(session.username, "ada");
+        assert_eq!(session.email, "ada@example.com");
+        assert_eq!(session.access_token, "access");
+        assert_eq!(session.refresh_token, "refresh");
+        assert!(!session.is_admin);
+    }
+
+    #[test]
+    fn keeps_admin_flag() {
+        let session =
+            Session::from_user(Ok(user(true)), "access".to_string(), "refresh".to_string())
+                .unwrap();
+
+        assert!(session.is_admin);
+    }
+
+    #[test]
+    fn failed_lookup_gives_formatted_error() {
+        let result = Session::from_user(
+            Err(unauthorized()),
+            "access".to_string(),
+            "refresh".to_string(),
+        );
+
+        assert_eq!(result.unwrap_err(), "401: Unauthorized");
+    }
+
+    fn unauthorized() -> ErrorResponse {
+        ErrorResponse {
+            message: "Unauthorized".to_string(),
+            status: 401,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn log_in_saves_session() {
+        for is_admin in [false, true] {
+            let mut saved = vec![];
+
+            let session = Session::finish_log_in(
+                Ok(user(is_admin)),
+                "access".to_string(),
+                "refresh".to_string(),
+                |s| {
+                    saved.push(s.clone());
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+            assert_eq!(saved.len(), 1);
+            assert_eq!(saved[0].uuid, "u-1");
+            assert_eq!(saved[0].access_token, "access");
+            assert_eq!(saved[0].is_admin, is_admin);
+            assert_eq!(session.is_admin, is_admin);
+        }
+    }
+
+    #[test]
+    fn failed_log_in_saves_nothing() {
+        let mut saves = 0;
+
+        let result = Session::finish_log_in(
+            Err(unauthorized()),
+            "access".to_string(),
+            "refresh".to_string(),
+            |_| {
+                saves += 1;
+                Ok(())
+            },
+        );
+
+        assert_eq!(result.unwrap_err(), "401: Unauthorized");
+        assert_eq!(saves, 0);
+    }
+
+    #[test]
+    fn log_in_reports_storage_failure() {
+        let result = Session::finish_log_in(
+            Ok(user(false)),
+            "access".to_string(),
+            "refresh".to_string(),
+            |_| Err("QuotaExceededError".to_string()),
+        );
+
+        assert_eq!(result.unwrap_err(), "QuotaExceededError");
+    }
+
+    #[test]
+    fn upgrades_v1_session() {
+        let v1 = serde_json::json!({
+            "uuid": "u-1",
+            "username": "ada",
+            "email": "ada@example.com",
+            "is_admin": true,
+            "access_token": "access",
+            "refresh_token": "refresh",
+        });
+
+        let session = upgrade_session(1, v1).unwrap();
+
+        assert_eq!(session.uuid, "u-1");
+        assert_eq!(session.username, "ada");
+        assert_eq!(session.email, "ada@example.com");
+        assert!(session.is_admin);
+        assert_eq!(session.access_token, "access");
+        assert_eq!(session.refresh_token, "refresh");
+    }
+
+    #[test]
+    fn unreadable_v1_session_is_dropped() {
+        let v1 = serde_json::json!({ "uuid": "u-1" });
+
+        assert!(upgrade_session(1, v1).is_none());
+    }
+
+    #[test]
+    fn unknown_version_is_dropped() {
+        let session = serde_json::to_value(
+            Session::from_user(Ok(user(false)), "access".to_string(), "refresh".to_string())
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert!(upgrade_session(0, session).is_none());
+    }
+
+    #[test]
+    fn newer_version_is_left_untouched() {
+        let stored = StoredSession {
+            version: SESSION_VERSION + 1,
+            session: serde_json::json!({ "shape": "from the future" }),
         };
 
-        session.save().map_err(|e| e.to_string())?;
-        Ok(session)
+        assert_eq!(
+            stored_session_action(&stored),
+            StoredSessionAction::LeaveForNewerDeploy
+        );
+    }
+
+    #[test]
+    fn older_version_is_upgraded_and_current_is_kept() {
+        let older = StoredSession {
+            version: 1,
+            session: serde_json::Value::Null,
+        };
+        let current = StoredSession {
+            version: SESSION_VERSION,
+            session: serde_json::Value::Null,
+        };
+
+        assert_eq!(stored_session_action(&older), StoredSessionAction::Upgrade);
+        assert_eq!(stored_session_action(&current), StoredSessionAction::Read);
+    }
+
+    #[test]
+    fn token_well_before_expiry_is_not_expiring() {
+        assert!(!expires_within(1_000.0, 0.0, 1_100.0, 30.0));
+    }
+
+    #[test]
+    fn token_inside_skew_is_expiring() {
+        assert!(expires_within(1_000.0, 0.0, 1_020.0, 30.0));
+        assert!(expires_within(1_000.0, 0.0, 1_030.0, 30.0));
+    }
+
+    #[test]
+    fn slow_local_clock_is_corrected_by_offset() {
+        // The local clock reads 1000 but the server is two minutes ahead
+        assert!(!expires_within(1_000.0, 0.0, 1_100.0, 30.0));
+        assert!(expires_within(1_000.0, 120.0, 1_100.0, 30.0));
+    }
+
+    #[test]
+    fn fast_local_clock_is_corrected_by_offset() {
+        // The local clock reads 1000 but the server is two minutes behind
+        assert!(expires_within(1_000.0, 0.0, 1_000.0, 30.0));
+        assert!(!expires_within(1_000.0, -120.0, 1_000.0, 30.0));
     }
 }
@@ -0,0 +1,85 @@
+use leptos::{ev, prelude::*};
+use web_sys::{KeyboardEvent, wasm_bindgen::JsCast};
+
+use crate::modal::Modal;
+
+pub struct Shortcut {
+    pub keys: &'static str,
+    pub description: &'static str,
+}
+
+/// Every keyboard shortcut in the app; the help overlay is rendered from this list
+pub const SHORTCUTS: &[Shortcut] = &[
+    Shortcut {
+        keys: "/",
+        description: "Focus the search box",
+    },
+    Shortcut {
+        keys: "Enter",
+        description: "Seek (from the search box)",
+    },
+    Shortcut {
+        keys: "← / →",
+        description: "Previous / next page of results",
+    },
+    Shortcut {
+        keys: "s",
+        description: "Switch between sorting by price and by $/kg",
+    },
+    Shortcut {
+        keys: "?",
+        description: "Show keyboard shortcuts",
+    },
+    Shortcut {
+        keys: "Esc",
+        description: "Close this dialog",
+    },
+];
+
+/// Whether a key press should be left alone because the user is typing or using a modifier combo
+pub fn should_ignore(e: &KeyboardEvent) -> bool {
+    if e.ctrl_key() || e.meta_key() || e.alt_key() {
+        return true;
+    }
+
+    let Some(element) = e
+        .target()
+        .and_then(|t| t.dyn_into::<web_sys::HtmlElement>().ok())
+    else {
+        return false;
+    };
+
+    matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+        || element.is_content_editable()
+}
+
+#[component]
+pub fn ShortcutHelp(open: RwSignal<bool>) -> impl IntoView {
+    let handle = window_event_listener(ev::keydown, move |e| {
+        if e.key() == "?" && !should_ignore(&e) {
+            e.prevent_default();
+            open.set(true);
+        }
+    });
+    on_cleanup(move || handle.remove());
+
+    view! {
+        <Modal open=open title="Keyboard shortcuts">
+            <table class="shortcut-table">
+                <tbody>
+                    {SHORTCUTS
+                        .iter()
+                        .map(|s| {
+                            view! {
+                                <tr>
+                                    <td><kbd>{s.keys}</kbd></td>
+                                    <td style="text-align: left;">{s.description}</td>
+                                </tr>
+                            }
+                        })
+                        .collect_view()}
+                </tbody>
+            </table>
+        </Modal>
+    }
+}
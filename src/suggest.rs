@@ -0,0 +1,143 @@
+use gloo_net::http::Method;
+use leptos::{ev, prelude::*, reactive::spawn_local};
+use serde::Serialize;
+
+use crate::{
+    modal::Modal,
+    product::{Retailer, is_well_formed_url},
+    request::{Auth, request_json},
+};
+
+#[derive(Serialize)]
+struct SuggestionBody {
+    url: String,
+    retailer: Option<String>,
+    note: Option<String>,
+}
+
+/// Lets visitors suggest a retailer or product page for the catalog
+#[component]
+pub fn SuggestRetailer(open: RwSignal<bool>) -> impl IntoView {
+    let (url, set_url) = signal(String::new());
+    let (retailer, set_retailer) = signal(String::new());
+    // Set once the visitor types a retailer name, so URL edits stop replacing it
+    let (retailer_overridden, set_retailer_overridden) = signal(false);
+    let (note, set_note) = signal(String::new());
+    let (sending, set_sending) = signal(false);
+    let (message, set_message) = signal::<Option<Result<String, String>>>(None);
+
+    let on_submit = move |e: ev::SubmitEvent| {
+        e.prevent_default();
+
+        let url = url.get_untracked().trim().to_string();
+
+        if !is_well_formed_url(&url) {
+            set_message.set(Some(Err(
+                "Enter a full link, starting with http:// or https://".to_string(),
+            )));
+            return;
+        }
+
+        let body = SuggestionBody {
+            url,
+            retailer: Some(retailer.get_untracked().trim().to_string()).filter(|r| !r.is_empty()),
+            note: Some(note.get_untracked().trim().to_string()).filter(|n| !n.is_empty()),
+        };
+
+        set_sending.set(true);
+        set_message.set(None);
+
+        spawn_local(async move {
+            match request_json::<SuggestionBody, ()>(
+                "suggestions",
+                Auth::Unauthorized,
+                Method::POST,
+                Some(&body),
+            )
+            .await
+            {
+                Ok(()) => {
+                    set_url.set(String::new());
+                    set_retailer.set(String::new());
+                    set_retailer_overridden.set(false);
+                    set_note.set(String::new());
+                    set_message.set(Some(Ok(
+                        "Thanks! We'll take a look at adding it.".to_string()
+                    )));
+                }
+                Err(e) => set_message.set(Some(Err(format!(
+                    "Couldn't send your suggestion: ({}) {}",
+                    e.status, e.message
+                )))),
+            }
+
+            set_sending.set(false);
+        });
+    };
+
+    view! {
+        <Modal open=open title="Suggest a retailer">
+            <form style="display: grid; gap: 12px; margin-top: 12px;" on:submit=on_submit>
+                <p class="hint">"Know a store or listing we're missing? Send us a link."</p>
+                <div>
+                    <label for="suggest-url">"Store or product link"</label>
+                    <input
+                        id="suggest-url"
+                        class="input"
+                        type="url"
+                        required
+                        placeholder="https://…"
+                        prop:value=move || url.get()
+                        on:input=move |e| {
+                            let v = event_target_value(&e);
+
+                            if !retailer_overridden.get_untracked() {
+                                set_retailer.set(
+                                    Retailer::guess_from_url(&v)
+                                        .map(|r| r.to_string())
+                                        .unwrap_or_default(),
+                                );
+                            }
+                            set_url.set(v);
+                        }
+                    />
+                </div>
+                <div>
+                    <label for="suggest-retailer">"Retailer (optional)"</label>
+                    <input
+                        id="suggest-retailer"
+                        class="input"
+                        type="text"
+                        prop:value=move || retailer.get()
+                        on:input=move |e| {
+                            set_retailer_overridden.set(true);
+                            set_retailer.set(event_target_value(&e));
+                        }
+                    />
+                </div>
+                <div>
+                    <label for="suggest-note">"Anything else? (optional)"</label>
+                    <textarea
+                        id="suggest-note"
+                        class="input"
+                        rows="3"
+                        prop:value=move || note.get()
+                        on:input=move |e| set_note.set(event_target_value(&e))
+                    ></textarea>
+                </div>
+                <button type="submit" disabled=move || sending.get()>
+                    {move || if sending.get() { "Sending…" } else { "Send suggestion" }}
+                </button>
+
+                {move || {
+                    message
+                        .get()
+                        .map(|m| match m {
+                            Ok(s) => view! { <p class="success" role="status">{s}</p> }.into_any(),
+                            Err(s) => view! { <p class="error" role="alert">{s}</p> }.into_any(),
+                        })
+                }}
+            </form>
+        </Modal>
+    }
+}
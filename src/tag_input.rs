@@ -0,0 +1,81 @@
+use leptos::prelude::*;
+
+use crate::product::normalize_tag;
+
+/// Free-text tag entry: Enter or a comma turns the typed text into a chip, × removes one
+#[component]
+pub fn TagInput(
+    #[prop(into)] tags: Signal<Vec<String>>,
+    #[prop(into)] set_tags: SignalSetter<Vec<String>>,
+    placeholder: &'static str,
+) -> impl IntoView {
+    let (draft, set_draft) = signal(String::new());
+
+    let commit = move |text: &str| {
+        let new_tags: Vec<String> = text.split(',').filter_map(normalize_tag).collect();
+
+        let mut t = tags.get_untracked();
+        for tag in new_tags {
+            if !t.contains(&tag) {
+                t.push(tag);
+            }
+        }
+        set_tags.set(t);
+        set_draft.set(String::new());
+    };
+
+    view! {
+        <div class="tag-input">
+            <For
+                each=move || tags.get()
+                key=|t| t.clone()
+                children=move |tag: String| {
+                    let remove = tag.clone();
+
+                    view! {
+                        <span class="tag">
+                            {tag.clone()}
+                            <button
+                                class="tag-remove"
+                                aria-label=format!("Remove tag {tag}")
+                                on:click=move |_| {
+                                    let mut t = tags.get_untracked();
+                                    t.retain(|x| *x != remove);
+                                    set_tags.set(t);
+                                }
+                            >
+                                "×"
+                            </button>
+                        </span>
+                    }
+                }
+            />
+            <input
+                class="input"
+                type="text"
+                placeholder=placeholder
+                prop:value=move || draft.get()
+                on:input=move |e| {
+                    let v = event_target_value(&e);
+
+                    if v.contains(',') {
+                        commit(&v);
+                    } else {
+                        set_draft.set(v);
+                    }
+                }
+                on:keydown=move |e| {
+                    if e.key() == "Enter" {
+                        e.prevent_default();
+                        commit(&draft.get_untracked());
+                    } else if e.key() == "Backspace" && draft.with_untracked(|d| d.is_empty()) {
+                        let mut t = tags.get_untracked();
+                        t.pop();
+                        set_tags.set(t);
+                    }
+                }
+                on:blur=move |_| commit(&draft.get_untracked())
+            />
+        </div>
+    }
+}
@@ -0,0 +1,95 @@
+use gloo_net::http::Method;
+use leptos::{prelude::*, reactive::spawn_local};
+use serde::Deserialize;
+
+use crate::{
+    product::{FilamentColor, FilamentMaterial, KNOWN_COLORS, KNOWN_MATERIALS},
+    request::{Auth, request_json},
+};
+
+/// The materials and colors offered in dropdowns, in display order. The server may add to and
+/// reorder the built-in lists; anything it adds that has no enum variant arrives as `Other`.
+#[derive(Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct Taxonomy {
+    pub materials: Vec<FilamentMaterial>,
+    pub colors: Vec<FilamentColor>,
+}
+
+impl Default for Taxonomy {
+    fn default() -> Self {
+        Self {
+            materials: KNOWN_MATERIALS.to_vec(),
+            colors: KNOWN_COLORS.to_vec(),
+        }
+    }
+}
+
+impl Taxonomy {
+    /// Drops entries that can't be chosen as a filter and falls back to the built-in list for
+    /// any list the server left empty
+    fn sanitized(self) -> Self {
+        fn clean<T: Clone + PartialEq>(
+            items: Vec<T>,
+            keep: impl Fn(&T) -> bool,
+            fallback: &[T],
+        ) -> Vec<T> {
+            let mut out: Vec<T> = vec![];
+
+            for item in items {
+                if keep(&item) && !out.contains(&item) {
+                    out.push(item);
+                }
+            }
+
+            if out.is_empty() {
+                fallback.to_vec()
+            } else {
+                out
+            }
+        }
+
+        Self {
+            materials: clean(
+                self.materials,
+                |m| match m {
+                    FilamentMaterial::Unspecified => false,
+                    FilamentMaterial::Other(s) => !s.trim().is_empty(),
+                    _ => true,
+                },
+                KNOWN_MATERIALS,
+            ),
+            colors: clean(
+                self.colors,
+                |c| match c {
+                    FilamentColor::Unspecified => false,
+                    FilamentColor::Other(s) => !s.trim().is_empty(),
+                    _ => true,
+                },
+                KNOWN_COLORS,
+            ),
+        }
+    }
+}
+
+/// Puts the built-in taxonomy in context, replacing it with the server's once `/taxonomy` loads.
+/// If the request fails the built-in lists stay in use.
+pub fn provide_taxonomy() {
+    let taxonomy = RwSignal::new(Taxonomy::default());
+    provide_context(taxonomy);
+
+    spawn_local(async move {
+        match request_json::<(), Taxonomy>("taxonomy", Auth::Unauthorized, Method::GET, None).await
+        {
+            Ok(fetched) => taxonomy.set(fetched.sanitized()),
+            Err(e) => crate::console_warn(format!(
+                "Using built-in materials and colors: ({}) {}",
+                e.status, e.message
+            )),
+        }
+    });
+}
+
+pub fn use_taxonomy() -> RwSignal<Taxonomy> {
+    use_context::<RwSignal<Taxonomy>>().expect("taxonomy should be provided by App")
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use leptos::prelude::*;
+
+/// How long a toast stays up before dismissing itself
+const TOAST_MS: u64 = 5000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+#[derive(Clone)]
+struct Toast {
+    id: u64,
+    kind: ToastKind,
+    message: String,
+    /// Runs the failed action again when the toast's Retry button is clicked
+    retry: Option<Callback<()>>,
+}
+
+/// Brief messages shown in the corner of the screen, shared through context
+#[derive(Clone, Copy)]
+pub struct Toasts {
+    items: RwSignal<Vec<Toast>>,
+    next_id: StoredValue<u64>,
+}
+
+impl Toasts {
+    pub fn success(&self, message: impl Into<String>) {
+        self.push(ToastKind::Success, message.into(), None);
+    }
+
+    pub fn error(&self, message: impl Into<String>) {
+        self.push(ToastKind::Error, message.into(), None);
+    }
+
+    /// An error with a Retry button; only for actions that are safe to repeat
+    pub fn error_with_retry(&self, message: impl Into<String>, retry: Callback<()>) {
+        self.push(ToastKind::Error, message.into(), Some(retry));
+    }
+
+    fn push(&self, kind: ToastKind, message: String, retry: Option<Callback<()>>) {
+        let id = self.next_id.get_value();
+        self.next_id.set_value(id + 1);
+        self.items.update(|t| {
+            t.push(Toast {
+                id,
+                kind,
+                message,
+                retry,
+            })
+        });
+
+        let toasts = *self;
+        set_timeout(move || toasts.dismiss(id), Duration::from_millis(TOAST_MS));
+    }
+
+    fn dismiss(&self, id: u64) {
+        self.items.update(|t| t.retain(|toast| toast.id != id));
+    }
+}
+
+pub fn provide_toasts() {
+    provide_context(Toasts {
+        items: RwSignal::new(vec![]),
+        next_id: StoredValue::new(0),
+    });
+}
+
+pub fn use_toasts() -> Toasts {
+    use_context::<Toasts>().expect("toasts should be provided by App")
+}
+
+#[component]
+pub fn ToastHost() -> impl IntoView {
+    let toasts = use_toasts();
+
+    view! {
+        <div class="toasts">
+            <For
+                each=move || toasts.items.get()
+                key=|t| t.id
+                children=move |t: Toast| {
+                    let id = t.id;
+                    let role = match t.kind {
+                        ToastKind::Success => "status",
+                        ToastKind::Error => "alert",
+                    };
+
+                    view! {
+                        <div class="toast" class:error=t.kind == ToastKind::Error role=role>
+                            <span>{t.message}</span>
+                            {t.retry.map(|retry| {
+                                view! {
+                                    <button
+                                        class="link-button"
+                                        on:click=move |_| {
+                                            toasts.dismiss(id);
+                                            retry.run(());
+                                        }
+                                    >
+                                        "Retry"
+                                    </button>
+                                }
+                            })}
+                            <button
+                                class="modal-close"
+                                aria-label="Dismiss"
+                                on:click=move |_| toasts.dismiss(id)
+                            >
+                                "×"
+                            </button>
+                        </div>
+                    }
+                }
+            />
+        </div>
+    }
+}
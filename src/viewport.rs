@@ -0,0 +1,65 @@
+use std::time::Duration;
+
+use leptos::{ev, prelude::*};
+
+/// Widest viewport given the compact results layout; keep in step with the `max-width` media
+/// query in style.css
+pub const NARROW_MAX_WIDTH: u32 = 800;
+/// At most one width update per this interval while the window is being resized
+const RESIZE_THROTTLE_MS: u64 = 150;
+
+#[derive(Clone, Copy)]
+struct ViewportWidth(ReadSignal<u32>);
+
+fn current_width() -> u32 {
+    window()
+        .inner_width()
+        .ok()
+        .and_then(|w| w.as_f64())
+        .map(|w| w as u32)
+        .unwrap_or(0)
+}
+
+/// Tracks the window's width in a signal shared through context. Resize events are throttled,
+/// so subscribers see at most one update per [`RESIZE_THROTTLE_MS`].
+pub fn provide_viewport_width() {
+    let (width, set_width) = signal(current_width());
+    let pending = StoredValue::new(None::<TimeoutHandle>);
+
+    let handle = window_event_listener(ev::resize, move |_| {
+        if pending.get_value().is_some() {
+            return;
+        }
+
+        let timeout = set_timeout_with_handle(
+            move || {
+                pending.set_value(None);
+
+                let current = current_width();
+                if width.get_untracked() != current {
+                    set_width.set(current);
+                }
+            },
+            Duration::from_millis(RESIZE_THROTTLE_MS),
+        )
+        .ok();
+        pending.set_value(timeout);
+    });
+
+    on_cleanup(move || {
+        handle.remove();
+
+        if let Some(timeout) = pending.get_value() {
+            timeout.clear();
+        }
+    });
+
+    provide_context(ViewportWidth(width));
+}
+
+/// The window's inner width in CSS pixels
+pub fn use_viewport_width() -> ReadSignal<u32> {
+    use_context::<ViewportWidth>()
+        .expect("viewport width should be provided by App")
+        .0
+}